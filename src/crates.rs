@@ -0,0 +1,53 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::rustdoc_json::RustDocJson;
+
+/// ----------------------------------------
+/// 外部クレートの表示名 ("serde 1.0.210")
+/// 版が分からなければ、同名のクレートと区別できるように ID を付ける ("serde #5")
+/// crate_id 0 (このクレート) や未知の ID なら None
+/// ----------------------------------------
+pub fn crate_label(doc: &RustDocJson, crate_id: u32) -> Option<String> {
+    let external = doc.external_crates.get(&crate_id.to_string())?;
+    Some(
+        match version_of(&external.name, external.html_root_url.as_deref()) {
+            Some(version) => format!("{} {}", external.name, version),
+            None => format!("{} #{}", external.name, crate_id),
+        },
+    )
+}
+
+/// docs.rs の URL ("https://docs.rs/serde/1.0.210/") から版を取り出す
+fn version_of(name: &str, html_root_url: Option<&str>) -> Option<String> {
+    let url = html_root_url?;
+    let rest = url.split(&format!("/{}/", name)).nth(1)?;
+    let version = rest.split('/').next()?;
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| version.to_string())
+}
+
+/// ----------------------------------------
+/// 与えられた crate_id の中で、同じ名前の別のクレート (別の版) が
+/// 混ざっているものの名前
+/// 片方の版の trait impl はもう片方の版の型には効かないので、混乱のもとになる
+/// ----------------------------------------
+pub fn conflicting_crates(
+    doc: &RustDocJson,
+    crate_ids: impl IntoIterator<Item = u32>,
+) -> Vec<String> {
+    let mut by_name: BTreeMap<&str, BTreeSet<u32>> = BTreeMap::new();
+    for crate_id in crate_ids {
+        if let Some(external) = doc.external_crates.get(&crate_id.to_string()) {
+            by_name
+                .entry(external.name.as_str())
+                .or_default()
+                .insert(crate_id);
+        }
+    }
+    by_name
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
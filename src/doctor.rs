@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::process::Command;
+
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+
+use crate::extractor;
+
+/// ----------------------------------------
+/// 不具合報告に貼ってもらうための環境情報
+/// ----------------------------------------
+pub fn environment() -> Vec<(&'static str, String)> {
+    let mut features = Vec::new();
+    if cfg!(feature = "source") {
+        features.push("source");
+    }
+    vec![
+        ("version", env!("CARGO_PKG_VERSION").to_string()),
+        ("rustc", tool_version("rustc")),
+        ("cargo", tool_version("cargo")),
+        (
+            "os",
+            format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        ),
+        (
+            "features",
+            if features.is_empty() {
+                "(none)".to_string()
+            } else {
+                features.join(", ")
+            },
+        ),
+        (
+            "command line",
+            redact(&std::env::args().collect::<Vec<_>>().join(" ")),
+        ),
+    ]
+}
+
+/// `rustc -V` などの1行目 (見つからなければその旨)
+fn tool_version(tool: &str) -> String {
+    match Command::new(tool).arg("-V").output() {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        _ => format!("({} not found)", tool),
+    }
+}
+
+/// ホームディレクトリを "~" に置き換える (ユーザー名などを貼らずに済むように)
+fn redact(text: &str) -> String {
+    match std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")) {
+        Ok(home) if !home.is_empty() => text.replace(&home, "~"),
+        _ => text.to_string(),
+    }
+}
+
+/// 中身を読み飛ばして件数と版だけ取るための形
+#[derive(Deserialize)]
+struct JsonHeader {
+    #[serde(default)]
+    format_version: Option<u32>,
+    #[serde(default)]
+    crate_version: Option<String>,
+    #[serde(default)]
+    index: HashMap<String, IgnoredAny>,
+}
+
+/// ----------------------------------------
+/// 入力ファイル1つ分の診断結果
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct InputReport {
+    pub path: String,
+    pub format_version: Option<u32>,
+    pub crate_version: Option<String>,
+    pub item_count: Option<usize>,
+    /// このツールで読めたか (読めなければエラーメッセージ)
+    pub load_result: Result<(), String>,
+}
+
+/// ----------------------------------------
+/// 入力を調べる
+/// まず版と件数だけを緩く読み、そのあと実際の読み込みを試す
+/// (型が合わずに読めない場合でも format_version は分かる)
+/// ----------------------------------------
+pub fn inspect_input(path: &Path) -> InputReport {
+    let header = if extractor::is_source_input(path) {
+        None
+    } else {
+        File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader::<_, JsonHeader>(BufReader::new(file)).ok())
+    };
+    InputReport {
+        path: redact(&path.display().to_string()),
+        format_version: header.as_ref().and_then(|h| h.format_version),
+        crate_version: header.as_ref().and_then(|h| h.crate_version.clone()),
+        item_count: header.as_ref().map(|h| h.index.len()),
+        load_result: extractor::extract(path)
+            .map(|_| ())
+            .map_err(|e| redact(&e.to_string())),
+    }
+}
+
+/// ----------------------------------------
+/// 報告用のテキストにする
+/// ----------------------------------------
+pub fn to_text(environment: &[(&str, String)], inputs: &[InputReport]) -> String {
+    let mut out = String::new();
+    for (key, value) in environment {
+        out.push_str(&format!("{:<13} {}\n", format!("{}:", key), value));
+    }
+    for input in inputs {
+        out.push_str(&format!("\ninput: {}\n", input.path));
+        if let Some(version) = input.format_version {
+            out.push_str(&format!("  format_version: {}\n", version));
+        }
+        if let Some(version) = &input.crate_version {
+            out.push_str(&format!("  crate_version: {}\n", version));
+        }
+        if let Some(count) = input.item_count {
+            out.push_str(&format!("  items: {}\n", count));
+        }
+        match &input.load_result {
+            Ok(()) => out.push_str("  load: ok\n"),
+            Err(message) => out.push_str(&format!("  load: failed: {}\n", message)),
+        }
+    }
+    out
+}
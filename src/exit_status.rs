@@ -0,0 +1,157 @@
+use std::io::Error as IoError;
+use std::process::ExitCode;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// ----------------------------------------
+/// 終了ステータスの取り決め
+/// ラッパーツールが結果を判別できるよう、値は固定
+/// ----------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// 結果が1件以上あった
+    Found = 0,
+    /// 正常に終わったが結果が0件だった
+    NoResults = 1,
+    /// 引数が不正 (clap の usage エラーと同じ値)
+    Usage = 2,
+    /// 入力ファイルが開けない / 読めない
+    InputError = 3,
+    /// lint で違反が見つかった
+    LintFailed = 4,
+    /// 出力先 (ファイル・HTTP・コマンド) やプラグインでの失敗
+    OutputError = 5,
+}
+
+impl Status {
+    /// 結果があったかどうかから Found / NoResults を選ぶ
+    pub fn from_found(found: bool) -> Self {
+        if found {
+            Status::Found
+        } else {
+            Status::NoResults
+        }
+    }
+
+    /// エラー出力に使う種別名
+    fn kind(self) -> &'static str {
+        match self {
+            Status::Found => "found",
+            Status::NoResults => "no_results",
+            Status::Usage => "usage",
+            Status::InputError => "input",
+            Status::LintFailed => "lint",
+            Status::OutputError => "output",
+        }
+    }
+}
+
+/// ----------------------------------------
+/// 終了ステータス付きのエラー
+/// `?` で IoError から変換したものは入力エラーとして扱う
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct Failure {
+    pub status: Status,
+    pub error: IoError,
+}
+
+impl Failure {
+    /// 出力やプラグインでのエラー
+    pub fn output(error: IoError) -> Self {
+        Failure {
+            status: Status::OutputError,
+            error,
+        }
+    }
+}
+
+impl From<IoError> for Failure {
+    fn from(error: IoError) -> Self {
+        Failure {
+            status: Status::InputError,
+            error,
+        }
+    }
+}
+
+impl From<Status> for ExitCode {
+    fn from(status: Status) -> Self {
+        ExitCode::from(status as u8)
+    }
+}
+
+/// エラーメッセージの出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// "error: ..." の1行
+    Human,
+    /// {"error": {"kind": ..., "message": ..., "exit_code": ...}}
+    Json,
+}
+
+#[derive(Serialize)]
+struct JsonError<'a> {
+    error: JsonErrorBody<'a>,
+}
+
+#[derive(Serialize)]
+struct JsonErrorBody<'a> {
+    kind: &'static str,
+    message: &'a str,
+    exit_code: u8,
+}
+
+/// ----------------------------------------
+/// エラーを stderr に出す
+/// ----------------------------------------
+pub fn report_error(format: ErrorFormat, status: Status, message: &str) {
+    match format {
+        ErrorFormat::Human => eprintln!("error: {}", message),
+        ErrorFormat::Json => {
+            let body = JsonError {
+                error: JsonErrorBody {
+                    kind: status.kind(),
+                    message,
+                    exit_code: status as u8,
+                },
+            };
+            // 文字列と数値だけなので失敗しない
+            eprintln!("{}", serde_json::to_string(&body).unwrap_or_default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error as IoError;
+
+    use super::{Failure, Status};
+
+    #[test]
+    fn codes_are_fixed() {
+        let codes: Vec<u8> = [
+            Status::Found,
+            Status::NoResults,
+            Status::Usage,
+            Status::InputError,
+            Status::LintFailed,
+            Status::OutputError,
+        ]
+        .iter()
+        .map(|&status| status as u8)
+        .collect();
+        assert_eq!(codes, [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn io_errors_are_input_errors_unless_marked_as_output() {
+        let input: Failure = IoError::other("missing").into();
+        assert_eq!(input.status, Status::InputError);
+        assert_eq!(
+            Failure::output(IoError::other("refused")).status,
+            Status::OutputError
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use std::fs;
+use std::io::{Error as IoError, ErrorKind};
+use std::path::Path;
+
+use serde_json::{Error as SerdeError, Value};
+
+use crate::rustdoc_json::{prefix_ids, RustDocJson};
+
+/// ----------------------------------------
+/// 入力から RustDocJson (共通のアイテムモデル) を取り出す
+/// バックエンドごとに実装する
+/// ----------------------------------------
+pub trait Extractor {
+    /// このバックエンドで扱える入力かどうか
+    fn accepts(&self, path: &Path) -> bool;
+
+    /// 入力を読み込んでアイテムを取り出す
+    fn extract(&self, path: &Path) -> Result<RustDocJson, IoError>;
+}
+
+/// rustdoc の --output-format json が出した JSON を読む
+pub struct RustdocJsonExtractor;
+
+impl Extractor for RustdocJsonExtractor {
+    fn accepts(&self, path: &Path) -> bool {
+        !is_source_input(path)
+    }
+
+    fn extract(&self, path: &Path) -> Result<RustDocJson, IoError> {
+        let bytes = fs::read(path).map_err(|e| {
+            IoError::new(
+                e.kind(),
+                format!("failed to open file '{}': {}", path.display(), e),
+            )
+        })?;
+        parse_documents(path, &bytes)
+    }
+}
+
+/// ----------------------------------------
+/// 1つ以上連結された rustdoc JSON を読む
+/// 2つ目以降のドキュメントは ID に "1:" などの接頭辞を付けてから1つにまとめる
+/// (ルートはドキュメントの順に並ぶ)
+/// 途中で切れていれば、どこで切れたかと再生成の案内をエラーにする
+/// ----------------------------------------
+fn parse_documents(path: &Path, bytes: &[u8]) -> Result<RustDocJson, IoError> {
+    let mut stream = serde_json::Deserializer::from_slice(bytes).into_iter::<Value>();
+    let mut merged: Option<RustDocJson> = None;
+
+    for number in 0.. {
+        let start = stream.byte_offset();
+        let mut value = match stream.next() {
+            None => break,
+            Some(Ok(value)) => value,
+            Some(Err(e)) => return Err(document_error(path, bytes, number, start, e)),
+        };
+        if number > 0 {
+            prefix_ids(&mut value, &format!("{}:", number));
+        }
+        let doc: RustDocJson = serde_json::from_value(value)
+            .map_err(|e| document_error(path, bytes, number, start, e))?;
+        match &mut merged {
+            Some(merged) => merged.merge(doc),
+            None => merged = Some(doc),
+        }
+    }
+
+    merged.ok_or_else(|| {
+        IoError::new(
+            ErrorKind::InvalidData,
+            format!("'{}' is empty; {}", path.display(), REGENERATE_HINT),
+        )
+    })
+}
+
+const REGENERATE_HINT: &str =
+    "regenerate it with `cargo +nightly rustdoc -- -Z unstable-options --output-format json`";
+
+/// 読めなかったドキュメントのエラーを、場所が分かるメッセージにする
+fn document_error(
+    path: &Path,
+    bytes: &[u8],
+    number: usize,
+    start: usize,
+    e: SerdeError,
+) -> IoError {
+    let which = if number == 0 {
+        String::new()
+    } else {
+        format!(" in document {} (starting at byte {})", number + 1, start)
+    };
+    if e.is_eof() {
+        IoError::new(
+            ErrorKind::UnexpectedEof,
+            format!(
+                "'{}' looks truncated: unexpected end of input at byte {}{}; {}",
+                path.display(),
+                bytes.len(),
+                which,
+                REGENERATE_HINT
+            ),
+        )
+    } else {
+        IoError::new(
+            ErrorKind::InvalidData,
+            format!("failed to parse '{}'{}: {}", path.display(), which, e),
+        )
+    }
+}
+
+/// .rs ファイル / ディレクトリを syn で読む
+#[cfg(feature = "source")]
+pub struct SourceExtractor;
+
+#[cfg(feature = "source")]
+impl Extractor for SourceExtractor {
+    fn accepts(&self, path: &Path) -> bool {
+        is_source_input(path)
+    }
+
+    fn extract(&self, path: &Path) -> Result<RustDocJson, IoError> {
+        crate::source_parser::parse_source(path)
+    }
+}
+
+/// .rs ファイルかディレクトリなら JSON ではなくソースとして扱う
+pub fn is_source_input(path: &Path) -> bool {
+    path.is_dir() || path.extension().is_some_and(|ext| ext == "rs")
+}
+
+/// 有効なバックエンド一覧 (前にあるものが優先)
+fn extractors() -> Vec<Box<dyn Extractor>> {
+    vec![
+        #[cfg(feature = "source")]
+        Box::new(SourceExtractor),
+        Box::new(RustdocJsonExtractor),
+    ]
+}
+
+/// ----------------------------------------
+/// 入力の種類からバックエンドを選んで読み込む
+/// ----------------------------------------
+pub fn extract(path: &Path) -> Result<RustDocJson, IoError> {
+    match extractors().into_iter().find(|ex| ex.accepts(path)) {
+        Some(extractor) => extractor.extract(path),
+        None => Err(IoError::new(
+            ErrorKind::Unsupported,
+            format!(
+                "'{}' looks like Rust source; rebuild with `--features source` to read it",
+                path.display()
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde_json::{json, Value};
+
+    use super::parse_documents;
+    use crate::crates::crate_label;
+    use crate::modules::module_trees;
+
+    /// 1つのモジュールに struct を1つ置いたクレート
+    /// paths にはその struct と、crate_id 1 の外部クレートのトレイトが入る
+    fn crate_doc(name: &str, external: (&str, &str)) -> Value {
+        json!({
+            "root": 0,
+            "index": {
+                "0": { "name": name, "docs": format!("{} の説明", name), "inner": { "module": { "is_crate": true, "items": [1] } } },
+                "1": { "name": "Foo", "inner": { "struct": { "kind": "unit", "impls": [] } } }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": [name], "kind": "module" },
+                "1": { "crate_id": 0, "path": [name, "Foo"], "kind": "struct" },
+                "2": { "crate_id": 1, "path": [external.0, "Trait"], "kind": "trait" }
+            },
+            "external_crates": {
+                "1": { "name": external.0, "html_root_url": external.1 }
+            }
+        })
+    }
+
+    fn concatenated(docs: &[Value]) -> Vec<u8> {
+        docs.iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .into_bytes()
+    }
+
+    #[test]
+    fn later_documents_get_prefixed_ids_and_their_own_root() {
+        let bytes = concatenated(&[
+            crate_doc("first", ("core", "https://doc.rust-lang.org/core/")),
+            crate_doc("second", ("core", "https://doc.rust-lang.org/core/")),
+        ]);
+        let doc = parse_documents(Path::new("in.json"), &bytes).unwrap();
+        assert_eq!(doc.index["1"].name.as_deref(), Some("Foo"));
+        assert_eq!(doc.index["1:1"].name.as_deref(), Some("Foo"));
+        assert_eq!(doc.paths["1:1"].path, ["second", "Foo"]);
+        assert_eq!(
+            doc.roots.iter().map(|id| id.0.as_str()).collect::<Vec<_>>(),
+            ["0", "1:0"]
+        );
+        let module = doc.index["1:0"].inner.module.as_ref().unwrap();
+        assert_eq!(module.items[0].0, "1:1");
+
+        let paths: Vec<String> = module_trees(&doc)
+            .into_iter()
+            .map(|tree| tree.path)
+            .collect();
+        assert_eq!(paths, ["first", "second"]);
+    }
+
+    #[test]
+    fn same_external_crate_keeps_one_crate_id() {
+        let bytes = concatenated(&[
+            crate_doc("first", ("core", "https://doc.rust-lang.org/core/")),
+            crate_doc("second", ("core", "https://doc.rust-lang.org/core/")),
+        ]);
+        let doc = parse_documents(Path::new("in.json"), &bytes).unwrap();
+        assert_eq!(doc.external_crates.len(), 1);
+        assert_eq!(doc.paths["1:2"].crate_id, 1);
+        assert_eq!(doc.paths["1:1"].crate_id, 0);
+    }
+
+    #[test]
+    fn different_external_crates_with_the_same_id_are_kept_apart() {
+        let bytes = concatenated(&[
+            crate_doc("first", ("serde", "https://docs.rs/serde/1.0.100/")),
+            crate_doc("second", ("serde", "https://docs.rs/serde/1.0.200/")),
+        ]);
+        let doc = parse_documents(Path::new("in.json"), &bytes).unwrap();
+        assert_eq!(doc.external_crates.len(), 2);
+        assert_eq!(doc.paths["2"].crate_id, 1);
+        let second = doc.paths["1:2"].crate_id;
+        assert_ne!(second, 1);
+        assert_eq!(crate_label(&doc, second).as_deref(), Some("serde 1.0.200"));
+    }
+
+    #[test]
+    fn truncated_second_document_names_where_it_starts() {
+        let first = crate_doc("first", ("core", "https://doc.rust-lang.org/core/")).to_string();
+        let bytes = format!("{}\n{{\"index\": {{", first).into_bytes();
+        let err = parse_documents(Path::new("in.json"), &bytes).unwrap_err();
+        assert!(err.to_string().contains("document 2"), "{}", err);
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+
+use crate::rustdoc_json::{attribute_to_string, RustDocJson};
+
+/// ----------------------------------------
+/// 1つの Cargo feature について分かったこと
+/// ----------------------------------------
+#[derive(Debug, Default)]
+pub struct FeatureInfo {
+    /// クレートルートのドキュメントに書かれた説明
+    pub description: Option<String>,
+    /// この feature でゲートされているアイテム名
+    pub items: Vec<String>,
+}
+
+/// ----------------------------------------
+/// クレート内の feature 一覧を作る
+/// ルートのドキュメントの "Features" 節に並んだ項目と、
+/// 各アイテムの #[doc(cfg(feature = "..."))] / cfg 属性の両方から集める
+/// ----------------------------------------
+pub fn collect_features(doc: &RustDocJson) -> BTreeMap<String, FeatureInfo> {
+    let mut features: BTreeMap<String, FeatureInfo> = BTreeMap::new();
+
+    let root_docs = doc
+        .roots
+        .iter()
+        .filter_map(|id| doc.index.get(&id.0))
+        .filter_map(|root| root.docs.as_deref());
+    for docs in root_docs {
+        for (name, description) in documented_features(docs) {
+            let info = features.entry(name).or_default();
+            if !description.is_empty() {
+                info.description = Some(description);
+            }
+        }
+    }
+
+    for item in doc.index.values() {
+        let Some(name) = &item.name else {
+            continue;
+        };
+        for attr in &item.attrs {
+            let attr_str = attribute_to_string(attr);
+            // doc(cfg(...)), cfg(...), CfgTrace(...) のいずれか
+            if !attr_str.to_lowercase().contains("cfg") {
+                continue;
+            }
+            for feature in cfg_features(&attr_str) {
+                let info = features.entry(feature).or_default();
+                if !info.items.contains(name) {
+                    info.items.push(name.clone());
+                }
+            }
+        }
+    }
+
+    for info in features.values_mut() {
+        info.items.sort();
+    }
+    features
+}
+
+/// ----------------------------------------
+/// cfg 属性の文字列から feature 名を抜き出す
+/// 例: #[doc(cfg(feature = "serde"))]
+///     #[attr = CfgTrace([NameValue { name: "feature", value: Some("serde"), .. }])]
+/// ----------------------------------------
+fn cfg_features(attr: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut rest = attr;
+    while let Some(pos) = rest.find("feature") {
+        rest = &rest[pos + "feature".len()..];
+        let after = rest.trim_start_matches('"').trim_start();
+        let value = if let Some(v) = after.strip_prefix('=') {
+            v.trim_start()
+        } else if let Some(v) = after.strip_prefix(", value: Some(") {
+            v
+        } else {
+            continue;
+        };
+        if let Some(quoted) = value.strip_prefix('"') {
+            if let Some(end) = quoted.find('"') {
+                let feature = quoted[..end].to_string();
+                if !found.contains(&feature) {
+                    found.push(feature);
+                }
+            }
+        }
+    }
+    found
+}
+
+/// ----------------------------------------
+/// ルートのドキュメントから "Features" 見出し以下の箇条書きを読む
+/// 例: * `serde` — Serialize / Deserialize の実装を有効にする
+/// ----------------------------------------
+fn documented_features(docs: &str) -> Vec<(String, String)> {
+    let mut result = Vec::new();
+    let mut section_level: Option<usize> = None;
+    let mut in_code_block = false;
+
+    for line in docs.lines() {
+        let trimmed = line.trim();
+        // コードブロック中の "# " は見出しではない
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        if trimmed.starts_with('#') {
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            if section_level.is_some_and(|current| level <= current) {
+                section_level = None;
+            }
+            if section_level.is_none() && trimmed.to_lowercase().contains("feature") {
+                section_level = Some(level);
+            }
+            continue;
+        }
+
+        if section_level.is_none() {
+            continue;
+        }
+        let Some(bullet) = trimmed
+            .strip_prefix("* ")
+            .or_else(|| trimmed.strip_prefix("- "))
+        else {
+            continue;
+        };
+        // 先頭の `name` や **name** を feature 名とみなす
+        let body = bullet.trim_start_matches(['*', '`']);
+        let name_len = body
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(body.len());
+        if name_len == 0 {
+            continue;
+        }
+        let name = body[..name_len].to_string();
+        let description = body[name_len..]
+            .trim_start_matches(['*', '`', ':', '-', '—', ' '])
+            .trim()
+            .to_string();
+        result.push((name, description));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{cfg_features, collect_features, documented_features};
+    use crate::rustdoc_json::RustDocJson;
+
+    #[test]
+    fn reads_feature_names_from_both_attribute_forms() {
+        assert_eq!(cfg_features(r#"#[doc(cfg(feature = "serde"))]"#), ["serde"]);
+        assert_eq!(
+            cfg_features(r#"#[cfg(all(feature = "a", any(feature = "b", feature = "a")))]"#),
+            ["a", "b"]
+        );
+        assert_eq!(
+            cfg_features(
+                r#"#[attr = CfgTrace([NameValue { name: "feature", value: Some("std"), .. }])]"#
+            ),
+            ["std"]
+        );
+        assert!(cfg_features(r#"#[cfg(unix)]"#).is_empty());
+    }
+
+    #[test]
+    fn reads_bullets_under_the_features_heading_only() {
+        let docs = "# Crate\n\
+                    * `not_a_feature` — outside the section\n\
+                    ## Feature flags\n\
+                    * `serde` — Serialize support\n\
+                    - **std** - uses the standard library\n\
+                    ```\n\
+                    # * `in_code` — inside a code block\n\
+                    ```\n\
+                    ## Examples\n\
+                    * `after` — next section";
+        let features: Vec<String> = documented_features(docs)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(features, ["serde", "std"]);
+    }
+
+    #[test]
+    fn merges_documented_and_gated_features() {
+        let doc: RustDocJson = serde_json::from_value(json!({
+            "root": 0,
+            "index": {
+                "0": {
+                    "name": "fx",
+                    "docs": "# Features\n* `serde` — Serialize support",
+                    "inner": { "module": { "is_crate": true, "items": [1] } }
+                },
+                "1": {
+                    "name": "to_json",
+                    "attrs": ["#[doc(cfg(feature = \"serde\"))]"],
+                    "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+                },
+                "2": {
+                    "name": "spawn",
+                    "attrs": [{ "other": "#[doc(cfg(feature = \"rt\"))]" }],
+                    "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+                }
+            }
+        }))
+        .unwrap();
+        let features = collect_features(&doc);
+        assert_eq!(features.keys().collect::<Vec<_>>(), ["rt", "serde"]);
+        assert_eq!(features["serde"].items, ["to_json"]);
+        assert!(features["serde"]
+            .description
+            .as_deref()
+            .is_some_and(|d| d.contains("Serialize")));
+        assert_eq!(features["rt"].items, ["spawn"]);
+        assert_eq!(features["rt"].description, None);
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+
+use crate::impl_kind::ImplKind;
+use crate::rustdoc_json::{attribute_to_string, is_doc_hidden, item_paths, RustDocJson};
+use crate::signature_builder::Type;
+
+/// ----------------------------------------
+/// 生成コードなどを結果から除くための条件
+/// ----------------------------------------
+#[derive(Debug, Default)]
+pub struct ItemFilter {
+    /// 除外するパスのパターン (例: "windows_sys::*", "*::__private::*")
+    pub exclude_paths: Vec<String>,
+    /// #[automatically_derived] な impl とそのメソッドを除くか
+    pub skip_generated: bool,
+    /// 空でなければ、この種類の impl のメソッドだけを残す (impl の外の関数は残す)
+    pub impl_kinds: Vec<ImplKind>,
+    /// #[doc(hidden)] なアイテムも残すか
+    pub include_hidden: bool,
+}
+
+/// ----------------------------------------
+/// 条件に合うアイテムを index から取り除く
+/// ----------------------------------------
+pub fn prune(doc: &mut RustDocJson, filter: &ItemFilter) {
+    let mut removed: HashSet<String> = HashSet::new();
+
+    if filter.skip_generated {
+        for (id, item) in &doc.index {
+            let Some(imp) = &item.inner.impl_ else {
+                continue;
+            };
+            let derived = item
+                .attrs
+                .iter()
+                .any(|attr| attribute_to_string(attr).contains("automatically_derived"));
+            if derived {
+                removed.insert(id.clone());
+                removed.extend(imp.items.iter().map(|child| child.0.clone()));
+            }
+        }
+    }
+
+    if !filter.impl_kinds.is_empty() {
+        for (id, item) in &doc.index {
+            let Some(imp) = &item.inner.impl_ else {
+                continue;
+            };
+            if !filter.impl_kinds.contains(&ImplKind::of(imp)) {
+                removed.insert(id.clone());
+                removed.extend(imp.items.iter().map(|child| child.0.clone()));
+            }
+        }
+    }
+
+    if !filter.include_hidden {
+        let hidden: Vec<String> = doc
+            .index
+            .iter()
+            .filter(|(_, item)| is_doc_hidden(item))
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in hidden {
+            remove_with_members(doc, &id, &mut removed);
+        }
+        // 隠された型への impl も、その型のメソッドとして出てこないように除く
+        for (id, item) in &doc.index {
+            let Some(imp) = &item.inner.impl_ else {
+                continue;
+            };
+            let Type::ResolvedPath { resolved_path } = &imp.for_ else {
+                continue;
+            };
+            if resolved_path
+                .id
+                .as_ref()
+                .is_some_and(|self_id| removed.contains(&self_id.0))
+            {
+                removed.insert(id.clone());
+                removed.extend(imp.items.iter().map(|child| child.0.clone()));
+            }
+        }
+    }
+
+    if !filter.exclude_paths.is_empty() {
+        for (id, path) in item_paths(doc) {
+            if filter
+                .exclude_paths
+                .iter()
+                .any(|pattern| glob_match(pattern, &path))
+            {
+                removed.insert(id);
+            }
+        }
+    }
+
+    // 中身をすべて除いた impl や、除いた型への impl は空のブロックとして残さない
+    for (id, item) in &doc.index {
+        let Some(imp) = &item.inner.impl_ else {
+            continue;
+        };
+        let emptied =
+            !imp.items.is_empty() && imp.items.iter().all(|child| removed.contains(&child.0));
+        let self_removed = match &imp.for_ {
+            Type::ResolvedPath { resolved_path } => resolved_path
+                .id
+                .as_ref()
+                .is_some_and(|self_id| removed.contains(&self_id.0)),
+            _ => false,
+        };
+        if emptied || self_removed {
+            removed.insert(id.clone());
+            removed.extend(imp.items.iter().map(|child| child.0.clone()));
+        }
+    }
+
+    doc.index.retain(|id, _| !removed.contains(id));
+}
+
+/// アイテムと、モジュール・トレイト・impl ならその中身をまとめて除く
+fn remove_with_members(doc: &RustDocJson, id: &str, removed: &mut HashSet<String>) {
+    if !removed.insert(id.to_string()) {
+        return;
+    }
+    let Some(item) = doc.index.get(id) else {
+        return;
+    };
+    let members = if let Some(module) = &item.inner.module {
+        &module.items
+    } else if let Some(trait_) = &item.inner.trait_ {
+        &trait_.items
+    } else if let Some(imp) = &item.inner.impl_ {
+        &imp.items
+    } else {
+        return;
+    };
+    for child in members {
+        remove_with_members(doc, &child.0, removed);
+    }
+}
+
+/// ----------------------------------------
+/// 簡易グロブ: `*` は任意の文字列 (空も可)、`?` は任意の1文字
+/// ----------------------------------------
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // 直前の * の位置と、その * が吸収し始めた text の位置
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // * にもう1文字吸収させてやり直す
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_match;
+
+    #[test]
+    fn star_matches_any_run_including_empty() {
+        assert!(glob_match("fx::*", "fx::parse"));
+        assert!(glob_match("fx::*", "fx::"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("fx::pa*e", "fx::parse"));
+        assert!(!glob_match("fx::pa*e", "fx::parser"));
+    }
+
+    #[test]
+    fn star_crosses_path_separators() {
+        assert!(glob_match("fx::inner::*", "fx::inner::Deep"));
+        assert!(glob_match("fx::inner::*", "fx::inner::Deep::new"));
+        assert!(glob_match("*::__private::*", "fx::a::__private::internal"));
+    }
+
+    #[test]
+    fn pattern_respects_segment_boundaries() {
+        assert!(!glob_match("fx::inner::*", "fx::inner"));
+        assert!(!glob_match("fx::inner::*", "fx::innermost::Deep"));
+        assert!(!glob_match("fx::inner", "fx::inner::Deep"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_char() {
+        assert!(glob_match("fx::?", "fx::a"));
+        assert!(!glob_match("fx::?", "fx::ab"));
+        assert!(!glob_match("fx::?", "fx::"));
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use clap::ValueEnum;
+
+use crate::rustdoc_json::{Impl, RustDocJson};
+
+/// ----------------------------------------
+/// impl ブロックの種類
+/// 並び順は rustdoc HTML の見出しの順
+/// (Implementations / Trait Implementations /
+///  Auto Trait Implementations / Blanket Implementations)
+/// ----------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum)]
+pub enum ImplKind {
+    /// `impl Foo { ... }`
+    Inherent,
+    /// `impl Trait for Foo { ... }`
+    Trait,
+    /// rustdoc が合成した auto trait (Send, Sync など) の impl
+    Auto,
+    /// `impl<T: Bound> Trait for T` が具体的な型に適用されたもの
+    Blanket,
+}
+
+impl ImplKind {
+    /// impl の種類を判定する
+    pub fn of(imp: &Impl) -> Self {
+        if imp.is_synthetic {
+            ImplKind::Auto
+        } else if imp.blanket_impl.is_some() {
+            ImplKind::Blanket
+        } else if imp.trait_.is_some() {
+            ImplKind::Trait
+        } else {
+            ImplKind::Inherent
+        }
+    }
+
+    /// 表示用の名前
+    pub fn label(self) -> &'static str {
+        match self {
+            ImplKind::Inherent => "inherent",
+            ImplKind::Trait => "trait",
+            ImplKind::Auto => "auto trait",
+            ImplKind::Blanket => "blanket",
+        }
+    }
+}
+
+/// ----------------------------------------
+/// メソッドのID -> それを含む impl の種類
+/// impl の外の関数は入らない
+/// ----------------------------------------
+pub fn method_impl_kinds(doc: &RustDocJson) -> HashMap<String, ImplKind> {
+    let mut kinds = HashMap::new();
+    for item in doc.index.values() {
+        let Some(imp) = &item.inner.impl_ else {
+            continue;
+        };
+        let kind = ImplKind::of(imp);
+        for child in &imp.items {
+            kinds.insert(child.0.clone(), kind);
+        }
+    }
+    kinds
+}
@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use crate::rustdoc_json::{resolved_path_string, RustDocJson};
+use crate::signature_builder::Type;
+
+/// 行になる型の種類
+const TYPE_KINDS: &[&str] = &["struct", "enum", "union"];
+
+/// ----------------------------------------
+/// 型 × trait の実装有無の表
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct ImplMatrix {
+    /// 列: 指定された trait 名
+    pub traits: Vec<String>,
+    /// 行: (型の完全パス, 各 trait を実装しているか)
+    pub rows: Vec<(String, Vec<bool>)>,
+}
+
+/// ----------------------------------------
+/// このクレートの公開型それぞれについて、指定 trait を
+/// 実装しているかどうかを調べる
+/// trait 名は "Debug" のような末尾の名前でも、
+/// "serde::Serialize" のような完全パスでもよい
+/// ----------------------------------------
+pub fn build_matrix(doc: &RustDocJson, traits: &[String]) -> ImplMatrix {
+    // (型ID, 列番号) の組
+    let mut implemented: HashSet<(String, usize)> = HashSet::new();
+
+    for item in doc.index.values() {
+        // `impl !Send for Ptr` は実装していないことを表す (rustdoc が合成したものも含む)
+        let Some(imp) = item.inner.impl_.as_ref().filter(|imp| !imp.is_negative) else {
+            continue;
+        };
+        let (
+            Some(trait_path),
+            Type::ResolvedPath {
+                resolved_path: for_path,
+            },
+        ) = (&imp.trait_, &imp.for_)
+        else {
+            continue;
+        };
+        let Some(for_id) = &for_path.id else {
+            continue;
+        };
+        let trait_full = resolved_path_string(doc, trait_path);
+        for (col, wanted) in traits.iter().enumerate() {
+            if trait_matches(wanted, &trait_path.name, &trait_full) {
+                implemented.insert((for_id.0.clone(), col));
+            }
+        }
+    }
+
+    let mut rows: Vec<(String, Vec<bool>)> = doc
+        .paths
+        .iter()
+        .filter(|(_, summary)| summary.crate_id == 0 && TYPE_KINDS.contains(&summary.kind.as_str()))
+        .map(|(id, summary)| {
+            let cells = (0..traits.len())
+                .map(|col| implemented.contains(&(id.clone(), col)))
+                .collect();
+            (summary.path.join("::"), cells)
+        })
+        .collect();
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+    ImplMatrix {
+        traits: traits.to_vec(),
+        rows,
+    }
+}
+
+/// 指定された trait 名が impl の trait と一致するか
+fn trait_matches(wanted: &str, written: &str, full_path: &str) -> bool {
+    if wanted.contains("::") {
+        let wanted = std_facade(wanted);
+        let full_path = std_facade(full_path);
+        full_path == wanted
+            || std_facade(written) == wanted
+            || full_path.ends_with(&format!("::{}", wanted))
+    } else {
+        last_segment(written) == wanted || last_segment(full_path) == wanted
+    }
+}
+
+/// core::fmt::Display と std::fmt::Display を同じものとして比べるため、
+/// core / alloc で始まるパスを std に寄せる
+fn std_facade(path: &str) -> String {
+    match path.split_once("::") {
+        Some(("core" | "alloc", rest)) => format!("std::{}", rest),
+        _ => path.to_string(),
+    }
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// ----------------------------------------
+/// Markdown の表にする
+/// ----------------------------------------
+pub fn to_markdown(matrix: &ImplMatrix) -> String {
+    let mut out = format!("| Type | {} |\n", matrix.traits.join(" | "));
+    out.push_str(&format!("|------|{}\n", "---|".repeat(matrix.traits.len())));
+    for (ty, cells) in &matrix.rows {
+        let marks: Vec<&str> = cells.iter().map(|&ok| if ok { "✓" } else { " " }).collect();
+        out.push_str(&format!("| {} | {} |\n", ty, marks.join(" | ")));
+    }
+    out
+}
+
+/// ----------------------------------------
+/// CSV にする (実装していれば yes / なければ no)
+/// ----------------------------------------
+pub fn to_csv(matrix: &ImplMatrix) -> String {
+    let mut out = format!("type,{}\n", matrix.traits.join(","));
+    for (ty, cells) in &matrix.rows {
+        let marks: Vec<&str> = cells
+            .iter()
+            .map(|&ok| if ok { "yes" } else { "no" })
+            .collect();
+        out.push_str(&format!("{},{}\n", ty, marks.join(",")));
+    }
+    out
+}
@@ -0,0 +1,62 @@
+use crate::rustdoc_json::{is_rust_abi, RustDocJson};
+
+/// ----------------------------------------
+/// lint で見つかった問題1つ分
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct LintViolation {
+    /// lint の名前 (例: missing-safety-doc)
+    pub lint: &'static str,
+    /// 対象アイテム名
+    pub item: String,
+    pub message: String,
+}
+
+/// ----------------------------------------
+/// クレート作者向けの lint をすべて実行する (アイテム名順)
+/// ----------------------------------------
+pub fn run_lints(doc: &RustDocJson) -> Vec<LintViolation> {
+    let mut violations = check_safety_docs(doc);
+    violations.sort_by(|a, b| a.item.cmp(&b.item).then_with(|| a.lint.cmp(b.lint)));
+    violations
+}
+
+/// ----------------------------------------
+/// missing-safety-doc:
+/// unsafe fn のドキュメントに "# Safety" 節があるか
+/// 他クレートのアイテムと extern ブロック内の宣言は対象外
+/// ----------------------------------------
+fn check_safety_docs(doc: &RustDocJson) -> Vec<LintViolation> {
+    let mut violations = Vec::new();
+
+    for item in doc.index.values() {
+        let Some(func) = &item.inner.function else {
+            continue;
+        };
+        if item.crate_id != 0 || !func.header.is_unsafe {
+            continue;
+        }
+        let is_foreign = !func.has_body && !is_rust_abi(&func.header.abi);
+        if is_foreign {
+            continue;
+        }
+        if item.docs.as_deref().is_some_and(has_safety_section) {
+            continue;
+        }
+
+        violations.push(LintViolation {
+            lint: "missing-safety-doc",
+            item: item.name.clone().unwrap_or_else(|| "unknown".to_string()),
+            message: "unsafe fn has no `# Safety` section in its docs".to_string(),
+        });
+    }
+
+    violations
+}
+
+fn has_safety_section(docs: &str) -> bool {
+    docs.lines().any(|line| {
+        let trimmed = line.trim();
+        trimmed.starts_with('#') && trimmed.trim_start_matches('#').trim() == "Safety"
+    })
+}
@@ -0,0 +1,204 @@
+use std::env;
+use std::io::Error as IoError;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::rustdoc_json::{item_to_decl_string, Item, RustDocJson, Span};
+use crate::signature_builder::{render_ident, RenderOptions};
+
+/// LSP の SymbolKind (使うものだけ)
+const SYMBOL_KIND_MODULE: u32 = 2;
+const SYMBOL_KIND_ENUM: u32 = 10;
+const SYMBOL_KIND_INTERFACE: u32 = 11;
+const SYMBOL_KIND_FUNCTION: u32 = 12;
+const SYMBOL_KIND_CONSTANT: u32 = 14;
+const SYMBOL_KIND_STRUCT: u32 = 23;
+
+/// ----------------------------------------
+/// LSP の SymbolInformation と同じ形
+/// (エディタ拡張がそのままシンボル UI に渡せる)
+/// ----------------------------------------
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolInformation {
+    pub name: String,
+    pub kind: u32,
+    pub location: Location,
+    /// シグネチャ文字列 (LSP の拡張フィールドとして "detail" に入れる)
+    pub detail: String,
+}
+
+/// LSP の Location
+#[derive(Debug, Serialize)]
+pub struct Location {
+    pub uri: String,
+    pub range: Range,
+}
+
+/// LSP の Range (行・列とも 0 始まり)
+#[derive(Debug, Serialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub character: usize,
+}
+
+/// ----------------------------------------
+/// span を持つ関数・構造体・列挙型・トレイト・定数・モジュールを
+/// SymbolInformation の列にする (ファイル・行順)
+/// 相対パスの filename は source_root からのパスとして解決する
+/// (source_root も相対パスならカレントディレクトリから)
+/// ----------------------------------------
+pub fn symbols(
+    doc: &RustDocJson,
+    source_root: &Path,
+    options: RenderOptions,
+) -> Result<Vec<SymbolInformation>, IoError> {
+    let source_root = env::current_dir()?.join(source_root);
+    let mut symbols: Vec<SymbolInformation> = doc
+        .index
+        .values()
+        .filter_map(|item| {
+            let span = item.span.as_ref()?;
+            let kind = symbol_kind(item)?;
+            let name = item.name.clone()?;
+            let detail = match item_to_decl_string(doc, item, options) {
+                Some(decl) => decl,
+                None => format!("mod {}", render_ident(&name)),
+            };
+            Some(SymbolInformation {
+                name,
+                kind,
+                location: location(span, &source_root),
+                detail,
+            })
+        })
+        .collect();
+
+    symbols.sort_by(|a, b| {
+        (&a.location.uri, a.location.range.start.line)
+            .cmp(&(&b.location.uri, b.location.range.start.line))
+    });
+    Ok(symbols)
+}
+
+/// アイテムの種類に対応する SymbolKind (シンボルとして出さない種類なら None)
+fn symbol_kind(item: &Item) -> Option<u32> {
+    let inner = &item.inner;
+    if inner.function.is_some() {
+        Some(SYMBOL_KIND_FUNCTION)
+    } else if inner.struct_.is_some() || inner.union.is_some() {
+        Some(SYMBOL_KIND_STRUCT)
+    } else if inner.enum_.is_some() {
+        Some(SYMBOL_KIND_ENUM)
+    } else if inner.trait_.is_some() {
+        Some(SYMBOL_KIND_INTERFACE)
+    } else if inner.constant.is_some() || inner.assoc_const.is_some() {
+        Some(SYMBOL_KIND_CONSTANT)
+    } else if inner.module.is_some() {
+        Some(SYMBOL_KIND_MODULE)
+    } else {
+        None
+    }
+}
+
+fn location(span: &Span, source_root: &Path) -> Location {
+    let path = source_root.join(&span.filename);
+    let path = path.canonicalize().unwrap_or(path);
+    Location {
+        uri: file_uri(&path),
+        range: Range {
+            start: position(span.begin),
+            end: position(span.end),
+        },
+    }
+}
+
+/// rustdoc の (行, 列) は 1 始まりなので 0 始まりに直す
+fn position((line, column): (usize, usize)) -> Position {
+    Position {
+        line: line.saturating_sub(1),
+        character: column.saturating_sub(1),
+    }
+}
+
+/// file:// URI にする (パス区切り以外の予約文字はパーセントエンコード)
+fn file_uri(path: &Path) -> String {
+    let raw = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !raw.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in raw.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::path::Path;
+
+    use serde_json::json;
+
+    use super::{symbols, SYMBOL_KIND_FUNCTION, SYMBOL_KIND_MODULE, SYMBOL_KIND_STRUCT};
+    use crate::rustdoc_json::RustDocJson;
+    use crate::signature_builder::RenderOptions;
+
+    fn doc() -> RustDocJson {
+        let span = |line: usize| json!({ "filename": "src/lib.rs", "begin": [line, 1], "end": [line, 10] });
+        serde_json::from_value(json!({
+            "root": 0,
+            "index": {
+                "0": { "name": "fx", "span": span(1), "inner": { "module": { "is_crate": true, "items": [1, 2] } } },
+                "1": { "name": "Foo", "span": span(3), "inner": { "struct": { "kind": "unit" } } },
+                "2": {
+                    "name": "make",
+                    "span": span(5),
+                    "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn lists_modules_and_types_with_their_kinds() {
+        let symbols = symbols(&doc(), Path::new("."), RenderOptions::default()).unwrap();
+        let kinds: Vec<(&str, u32, &str)> = symbols
+            .iter()
+            .map(|symbol| (symbol.name.as_str(), symbol.kind, symbol.detail.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("fx", SYMBOL_KIND_MODULE, "mod fx"),
+                ("Foo", SYMBOL_KIND_STRUCT, "struct Foo;"),
+                ("make", SYMBOL_KIND_FUNCTION, "fn make()"),
+            ]
+        );
+    }
+
+    #[test]
+    fn relative_source_root_resolves_to_an_absolute_uri() {
+        // 存在しないディレクトリでも canonicalize できないだけで、カレントディレクトリから解決する
+        let symbols = symbols(&doc(), Path::new("no-such-dir"), RenderOptions::default()).unwrap();
+        let expected = env::current_dir().unwrap().join("no-such-dir/src/lib.rs");
+        assert_eq!(
+            symbols[0].location.uri,
+            format!("file://{}", expected.display())
+        );
+    }
+}
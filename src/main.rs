@@ -1,39 +1,812 @@
-use std::fs::File;
-use std::io::{BufReader, Error as IoError};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use clap::Parser;
-use serde_json::Error as SerdeError;
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
 
+mod crates;
+mod doctor;
+mod exit_status;
+mod extractor;
+mod features;
+mod filter;
+mod impl_kind;
+mod impl_matrix;
+mod lint;
+mod lsp;
+mod minify;
+mod modules;
+mod no_std;
+mod operators;
+mod panics;
+mod plugin;
 mod rustdoc_json;
+mod show;
 mod signature_builder;
+mod sink;
+#[cfg(feature = "source")]
+mod source_parser;
+mod stats;
+mod summary;
+mod template;
+mod usage;
 
-use rustdoc_json::{RustDocJson, item_to_signature_string};
+use exit_status::{report_error, ErrorFormat, Failure, Status};
+use rustdoc_json::{
+    generated_impl_item_ids, item_kind, item_paths, item_to_decl_string, nested_item_ids,
+    RustDocJson,
+};
+use signature_builder::{render_path, RenderOptions};
+use template::Template;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
+#[command(
+    after_help = "Exit status: 0 results found, 1 no results, 2 usage error, \
+3 input error, 4 lint violations, 5 output or plugin error"
+)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// Path to rustdoc JSON file (e.g., target/doc/crate_name/crate_name.json)
-    #[arg(value_name = "RUSTDOC_JSON_PATH")]
-    json_path: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Path to rustdoc JSON file (e.g., target/doc/crate_name/crate_name.json),
+    /// or a .rs file / source directory (requires the `source` feature)
+    #[arg(value_name = "INPUT", required = true)]
+    input: Option<PathBuf>,
+
+    /// Output format for the signature listing
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Directory that relative span file names are resolved against
+    /// (the directory rustdoc was run from; used by `--format lsp`)
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    source_root: PathBuf,
+
+    /// Format each listed item with a template instead of the bare signature.
+    /// Fields: {kind} {name} {path} {sig} {doc_summary} {file} {line} {impl}
+    /// {module} ({impl} is the kind of impl a method comes from, empty for free
+    /// functions); write `{{` / `}}` for literal braces
+    #[arg(long, value_name = "TEMPLATE", conflicts_with = "format")]
+    template: Option<String>,
+
+    /// Write the listing to DEST instead of stdout: `-` for stdout, an
+    /// `http://` URL to POST to, `|COMMAND` to pipe it to a command's stdin,
+    /// or a file path. Prefix with `FORMAT=` to pick a format other than
+    /// `--format`; may be given multiple times, e.g.
+    /// `-o text=- -o lsp=symbols.json`
+    #[arg(short, long, value_name = "[FORMAT=]DEST", value_parser = parse_output_spec)]
+    output: Vec<OutputSpec>,
+
+    /// Group the text listing by module, with each module's path and doc
+    /// summary (`//! ...`) as a header
+    #[arg(long)]
+    group_by_module: bool,
+
+    /// Don't print the summary of extracted item counts to stderr
+    #[arg(short, long)]
+    quiet: bool,
+
+    #[command(flatten)]
+    filter: FilterArgs,
+
+    /// Write `-> ()` in signatures of functions that return the unit type
+    /// (omitted by default, as in source code)
+    #[arg(long, global = true)]
+    show_unit_return: bool,
+
+    /// Omit lifetimes from signatures (`Cow<'a, str>` becomes `Cow<str>`,
+    /// `&'a T` becomes `&T`)
+    #[arg(long, global = true)]
+    elide_lifetimes: bool,
+
+    /// How errors are written to stderr
+    #[arg(long, value_enum, global = true, default_value_t = ErrorFormat::Human)]
+    error_format: ErrorFormat,
+}
+
+/// Options for leaving generated noise out of the results
+#[derive(ClapArgs)]
+struct FilterArgs {
+    /// Exclude items whose full path matches this glob (`*` matches anything,
+    /// including `::`); may be given multiple times, e.g. `windows_sys::*`
+    #[arg(long, value_name = "GLOB")]
+    exclude_path: Vec<String>,
+
+    /// Exclude `#[automatically_derived]` impls and the methods they contain
+    #[arg(long)]
+    skip_generated: bool,
+
+    /// Keep only methods from impls of these kinds (free functions are always
+    /// kept); comma separated or given multiple times
+    #[arg(long, value_enum, value_name = "KIND", value_delimiter = ',')]
+    impl_kind: Vec<impl_kind::ImplKind>,
+
+    /// Keep items marked `#[doc(hidden)]` and their contents (rustdoc only
+    /// emits them when run with `--document-hidden-items`)
+    #[arg(long)]
+    include_hidden: bool,
+
+    /// Filter items through an external command: it receives one JSON object
+    /// per item on stdin ({"id","name","path","sig","docs"}) and prints the ids
+    /// of the items to keep, one per line; quote arguments that contain
+    /// spaces; may be given multiple times
+    #[arg(long, value_name = "COMMAND")]
+    plugin_filter: Vec<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum MatrixFormat {
+    Markdown,
+    Csv,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// One Rust-like signature per line
+    Text,
+    /// JSON array of LSP `SymbolInformation` objects built from span data
+    Lsp,
 }
 
-fn main() -> Result<(), IoError> {
-    let args = Args::parse();
+impl OutputFormat {
+    /// HTTP で送るときの Content-Type
+    fn content_type(self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text/plain; charset=utf-8",
+            OutputFormat::Lsp => "application/json",
+        }
+    }
+}
 
-    let file = File::open(&args.json_path).map_err(|e| {
-        eprintln!("Failed to open file '{}': {}", args.json_path.display(), e);
-        e
-    })?;
-    let reader = BufReader::new(file);
-    let doc: RustDocJson = serde_json::from_reader(reader)
-        .map_err(|e: SerdeError| IoError::new(std::io::ErrorKind::Other, e.to_string()))?;
+/// `--output` の1つ分 (形式を省略したら `--format` に従う)
+#[derive(Clone)]
+struct OutputSpec {
+    format: Option<OutputFormat>,
+    dest: String,
+}
 
-    for item in doc.index.values() {
-        if let Some(sig_str) = item_to_signature_string(item) {
-            println!("{}", sig_str);
+/// "[FORMAT=]DEST" を解釈する
+/// "=" の前が形式名でなければ全体を DEST とみなす (URL のクエリなど)
+fn parse_output_spec(spec: &str) -> Result<OutputSpec, String> {
+    if let Some((name, dest)) = spec.split_once('=') {
+        if let Ok(format) = OutputFormat::from_str(name, true) {
+            if dest.is_empty() {
+                return Err(format!("missing destination after `{}=`", name));
+            }
+            return Ok(OutputSpec {
+                format: Some(format),
+                dest: dest.to_string(),
+            });
         }
     }
+    if spec.is_empty() {
+        return Err("empty output destination".to_string());
+    }
+    Ok(OutputSpec {
+        format: None,
+        dest: spec.to_string(),
+    })
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the crate's Cargo features and the items each one gates
+    Features {
+        #[arg(value_name = "INPUT")]
+        input: PathBuf,
+    },
+    /// Report which public items need `std` or `alloc` rather than only `core`
+    NoStd {
+        #[arg(value_name = "INPUT")]
+        input: PathBuf,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Check crate-author-facing rules (e.g. `# Safety` docs on unsafe fns);
+    /// exits with status 4 if any rule is violated
+    Lint {
+        #[arg(value_name = "INPUT")]
+        input: PathBuf,
+    },
+    /// Show which of the crate's public types implement the given traits
+    ImplMatrix {
+        #[arg(value_name = "INPUT")]
+        input: PathBuf,
+        /// Comma-separated trait names (e.g. Debug,Clone,serde::Serialize)
+        #[arg(long, value_delimiter = ',', required = true)]
+        traits: Vec<String>,
+        /// Table format
+        #[arg(long, value_enum, default_value_t = MatrixFormat::Markdown)]
+        format: MatrixFormat,
+    },
+    /// Show index statistics such as the most-referenced types
+    Stats {
+        #[arg(value_name = "INPUT")]
+        input: PathBuf,
+        /// How many of the most-referenced types to show
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// List functions that may panic (documented `# Panics` or `unwrap_*`/`expect_*` names)
+    Panics {
+        #[arg(value_name = "INPUT")]
+        input: PathBuf,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Print environment details for bug reports: tool and toolchain versions,
+    /// enabled features, and the format_version and load status of each INPUT
+    Doctor {
+        #[arg(value_name = "INPUT")]
+        inputs: Vec<PathBuf>,
+    },
+    /// Print the crate's module tree with each module's doc summary
+    Modules {
+        #[arg(value_name = "INPUT")]
+        input: PathBuf,
+    },
+    /// Print a shareable copy of a rustdoc JSON: docs are stripped and the crate's
+    /// own item names are replaced (`Item3`, `item_4`), keeping its structure
+    /// (for benchmark fixtures and bug reports)
+    Minify {
+        #[arg(value_name = "INPUT")]
+        input: PathBuf,
+    },
+    /// Show the details and docs of items matching a name or path
+    /// (functions, and std's documented primitives and keywords such as `u32` or `match`)
+    Show {
+        #[arg(value_name = "INPUT")]
+        input: PathBuf,
+        /// Item name or path, e.g. `parse`, `my_crate::parse`, `u32`, `match`;
+        /// names given with `#[doc(alias = "...")]` also match. An operator such
+        /// as `+`, `[]`, `==` or `*deref` finds the trait methods implementing it
+        /// (`Add::add`, `Index::index`, ...)
+        #[arg(value_name = "NAME")]
+        name: String,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+}
+
+/// ----------------------------------------
+/// 終了ステータス:
+///   0 結果あり / 1 結果なし / 2 引数エラー / 3 入力エラー / 4 lint 違反
+///   5 出力先・プラグインのエラー
+/// ----------------------------------------
+fn main() -> ExitCode {
+    let args = match Args::try_parse() {
+        Ok(args) => args,
+        // --help / --version はそのまま、usage エラーは形式を合わせて出す
+        Err(e) if e.use_stderr() && wants_json_errors() => {
+            // 1行目 ("error: ..." ) だけを本文にする
+            let message = e.to_string();
+            let first_line = message.lines().next().unwrap_or_default();
+            let first_line = first_line.strip_prefix("error: ").unwrap_or(first_line);
+            report_error(ErrorFormat::Json, Status::Usage, first_line);
+            return Status::Usage.into();
+        }
+        Err(e) => e.exit(),
+    };
+
+    let error_format = args.error_format;
+    match run(args) {
+        Ok(status) => status.into(),
+        Err(failure) => {
+            report_error(error_format, failure.status, &failure.error.to_string());
+            failure.status.into()
+        }
+    }
+}
+
+/// 引数のパースに失敗したときに --error-format json が指定されていたか
+fn wants_json_errors() -> bool {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter().any(|arg| arg == "--error-format=json")
+        || args
+            .windows(2)
+            .any(|pair| pair[0] == "--error-format" && pair[1] == "json")
+}
+
+fn run(args: Args) -> Result<Status, Failure> {
+    let options = RenderOptions {
+        show_unit_return: args.show_unit_return,
+        elide_lifetimes: args.elide_lifetimes,
+    };
+    let status = match args.command {
+        Some(Command::Features { input }) => {
+            let doc = extractor::extract(&input)?;
+            Status::from_found(print_features(&doc))
+        }
+        Some(Command::NoStd { input, filter }) => {
+            let doc = load(&input, &filter, options)?;
+            Status::from_found(print_no_std_report(&doc))
+        }
+        Some(Command::Lint { input }) => {
+            let doc = extractor::extract(&input)?;
+            let violations = lint::run_lints(&doc);
+            for v in &violations {
+                println!("{}: {}: {}", v.lint, v.item, v.message);
+            }
+            if violations.is_empty() {
+                Status::Found
+            } else {
+                eprintln!("{} lint violation(s) found", violations.len());
+                Status::LintFailed
+            }
+        }
+        Some(Command::ImplMatrix {
+            input,
+            traits,
+            format,
+        }) => {
+            let doc = extractor::extract(&input)?;
+            let matrix = impl_matrix::build_matrix(&doc, &traits);
+            match format {
+                MatrixFormat::Markdown => print!("{}", impl_matrix::to_markdown(&matrix)),
+                MatrixFormat::Csv => print!("{}", impl_matrix::to_csv(&matrix)),
+            }
+            Status::from_found(!matrix.rows.is_empty())
+        }
+        Some(Command::Stats { input, top, filter }) => {
+            let doc = load(&input, &filter, options)?;
+            Status::from_found(print_stats(&doc, top))
+        }
+        Some(Command::Panics { input, filter }) => {
+            let doc = load(&input, &filter, options)?;
+            Status::from_found(print_panics_report(&doc))
+        }
+        Some(Command::Doctor { inputs }) => {
+            let reports: Vec<doctor::InputReport> = inputs
+                .iter()
+                .map(|input| doctor::inspect_input(input))
+                .collect();
+            print!("{}", doctor::to_text(&doctor::environment(), &reports));
+            // 読めない入力があれば入力エラーとして終わる
+            if reports.iter().any(|report| report.load_result.is_err()) {
+                Status::InputError
+            } else {
+                Status::Found
+            }
+        }
+        Some(Command::Modules { input }) => {
+            let doc = extractor::extract(&input)?;
+            Status::from_found(print_module_tree(&doc))
+        }
+        Some(Command::Minify { input }) => {
+            for document in minify::minify_file(&input)? {
+                println!("{}", document);
+            }
+            Status::Found
+        }
+        Some(Command::Show {
+            input,
+            name,
+            filter,
+        }) => {
+            let doc = load(&input, &filter, options)?;
+            Status::from_found(print_show(&doc, &name, options))
+        }
+        None => {
+            // subcommand がなければ INPUT は clap が必須にしている
+            let input = args.input.expect("INPUT is required");
+            let template = match args.template.as_deref().map(Template::parse) {
+                Some(Ok(template)) => Some(template),
+                Some(Err(message)) => {
+                    report_error(args.error_format, Status::Usage, &message);
+                    return Ok(Status::Usage);
+                }
+                None => None,
+            };
+            // 指定がなければ --format で stdout に出す
+            let outputs = if args.output.is_empty() {
+                vec![OutputSpec {
+                    format: None,
+                    dest: "-".to_string(),
+                }]
+            } else {
+                args.output
+            };
+            // 出力先の指定の誤り (https:// など) は入力を読む前に知らせる
+            let mut sinks = Vec::new();
+            for output in &outputs {
+                match sink::open_sink(&output.dest) {
+                    Ok(sink) => sinks.push(sink),
+                    Err(e) => {
+                        report_error(args.error_format, Status::Usage, &e.to_string());
+                        return Ok(Status::Usage);
+                    }
+                }
+            }
+            let doc = load(&input, &args.filter, options)?;
+
+            let mut found = false;
+            for (output, sink) in outputs.iter().zip(&mut sinks) {
+                let format = output.format.unwrap_or(args.format);
+                let (content, any) = render_listing(
+                    &doc,
+                    format,
+                    template.as_ref(),
+                    args.group_by_module,
+                    &args.source_root,
+                    options,
+                )
+                .map_err(Failure::output)?;
+                found |= any;
+                sink.write(&content, format.content_type())
+                    .map_err(Failure::output)?;
+            }
+            // 一覧と混ざらないように stderr に出す
+            if !args.quiet {
+                eprint!("{}", summary::to_text(&summary::summarize(&doc)));
+            }
+            Status::from_found(found)
+        }
+    };
+
+    Ok(status)
+}
+
+/// 入力を読み込み、フィルタに合うアイテムを取り除く
+fn load(input: &Path, filter: &FilterArgs, options: RenderOptions) -> Result<RustDocJson, Failure> {
+    let mut doc = extractor::extract(input)?;
+    filter::prune(
+        &mut doc,
+        &filter::ItemFilter {
+            exclude_paths: filter.exclude_path.clone(),
+            skip_generated: filter.skip_generated,
+            impl_kinds: filter.impl_kind.clone(),
+            include_hidden: filter.include_hidden,
+        },
+    );
+    for command in &filter.plugin_filter {
+        let mut plugin = plugin::ExternalFilter {
+            command: command.clone(),
+        };
+        plugin::apply_filter(&mut doc, &mut plugin, options).map_err(Failure::output)?;
+    }
+    Ok(doc)
+}
 
-    Ok(())
+/// ----------------------------------------
+/// シグネチャ一覧を指定の形式で文字列にする
+/// 1件以上あったかどうかも返す
+/// ----------------------------------------
+fn render_listing(
+    doc: &RustDocJson,
+    format: OutputFormat,
+    template: Option<&Template>,
+    group_by_module: bool,
+    source_root: &Path,
+    options: RenderOptions,
+) -> Result<(String, bool), IoError> {
+    match format {
+        OutputFormat::Text => Ok(render_signatures(doc, template, group_by_module, options)),
+        OutputFormat::Lsp => {
+            let symbols = lsp::symbols(doc, source_root, options)?;
+            let mut json = serde_json::to_string_pretty(&symbols).map_err(IoError::other)?;
+            json.push('\n');
+            Ok((json, !symbols.is_empty()))
+        }
+    }
+}
+
+fn render_signatures(
+    doc: &RustDocJson,
+    template: Option<&Template>,
+    group_by_module: bool,
+    options: RenderOptions,
+) -> (String, bool) {
+    let paths = if template.is_some() || group_by_module {
+        item_paths(doc)
+    } else {
+        HashMap::new()
+    };
+    let impl_kinds = if template.is_some() {
+        impl_kind::method_impl_kinds(doc)
+    } else {
+        HashMap::new()
+    };
+    let (modules, item_modules) = if template.is_some() || group_by_module {
+        (modules::crate_modules(doc), modules::item_modules(doc))
+    } else {
+        (BTreeMap::new(), HashMap::new())
+    };
+    let use_paths = if template.is_some() {
+        modules::use_paths(doc)
+    } else {
+        HashMap::new()
+    };
+    // テンプレートなしではトレイトのメソッドなどは宣言の中に出す
+    // テンプレートでは1件ずつ出すが、blanket impl などの中身は出さない
+    let nested = if template.is_none() {
+        nested_item_ids(doc)
+    } else {
+        generated_impl_item_ids(doc)
+    };
+    // (属するモジュール, 行)
+    let mut lines: Vec<(Option<&str>, String)> = Vec::new();
+    for (id, item) in &doc.index {
+        // impl ブロックは名前がないので、テンプレートでの1件ずつの出力には出さない
+        if nested.contains(id)
+            || item.crate_id != 0
+            || (template.is_some() && item.inner.impl_.is_some())
+        {
+            continue;
+        }
+        let Some(sig_str) = item_to_decl_string(doc, item, options) else {
+            continue;
+        };
+        let module = item_modules.get(id).map(String::as_str);
+        let Some(template) = template else {
+            lines.push((module, sig_str));
+            continue;
+        };
+
+        let name = item.name.clone().unwrap_or_default();
+        let line = template.render(|field| match field {
+            "kind" => item_kind(item).unwrap_or_default().to_string(),
+            "name" => name.clone(),
+            // 一覧の宣言と同じく、キーワードと同じ名前は r# を付ける
+            "path" => render_path(paths.get(id).or_else(|| use_paths.get(id)).unwrap_or(&name)),
+            "sig" => sig_str.clone(),
+            "doc_summary" => item
+                .docs
+                .as_deref()
+                .map(template::doc_summary)
+                .unwrap_or_default(),
+            "file" => item
+                .span
+                .as_ref()
+                .map(|span| span.filename.display().to_string())
+                .unwrap_or_default(),
+            "line" => item
+                .span
+                .as_ref()
+                .map(|span| span.begin.0.to_string())
+                .unwrap_or_default(),
+            "module" => render_path(module.unwrap_or_default()),
+            "impl" => impl_kinds
+                .get(id)
+                .map(|kind| kind.label().to_string())
+                .unwrap_or_default(),
+            _ => String::new(),
+        });
+        lines.push((module, line));
+    }
+    let found = !lines.is_empty();
+
+    let mut out = String::new();
+    if !group_by_module {
+        for (_, line) in lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        return (out, found);
+    }
+
+    // モジュールごとにまとめ、モジュールの要約を見出しにする
+    // (どのモジュールにも属さないものは最後)
+    let mut groups: BTreeMap<(bool, &str), Vec<String>> = BTreeMap::new();
+    for (module, line) in lines {
+        groups
+            .entry((module.is_none(), module.unwrap_or_default()))
+            .or_default()
+            .push(line);
+    }
+    for (i, ((_, module), mut group)) in groups.into_iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let summary = modules
+            .get(module)
+            .and_then(|docs| docs.as_deref())
+            .map(template::doc_summary)
+            .filter(|summary| !summary.is_empty());
+        match (module, summary) {
+            ("", _) => out.push_str("(outside this crate's modules)\n"),
+            (module, Some(summary)) => out.push_str(&format!("{} — {}\n", module, summary)),
+            (module, None) => out.push_str(&format!("{}\n", module)),
+        }
+        group.sort();
+        for line in group {
+            for part in line.lines() {
+                out.push_str(&format!("    {}\n", part));
+            }
+        }
+    }
+    (out, found)
+}
+
+fn print_module_tree(doc: &RustDocJson) -> bool {
+    fn walk(tree: &modules::ModuleTree, depth: usize) {
+        let name = tree.path.rsplit("::").next().unwrap_or_default();
+        let summary = tree
+            .docs
+            .as_deref()
+            .map(template::doc_summary)
+            .unwrap_or_default();
+        if summary.is_empty() {
+            println!("{}{}", "  ".repeat(depth), name);
+        } else {
+            println!("{}{} — {}", "  ".repeat(depth), name, summary);
+        }
+        for child in &tree.children {
+            walk(child, depth + 1);
+        }
+    }
+    let trees = modules::module_trees(doc);
+    for tree in &trees {
+        walk(tree, 0);
+    }
+    !trees.is_empty()
+}
+
+fn print_show(doc: &RustDocJson, name: &str, options: RenderOptions) -> bool {
+    let details = show::find(doc, name, options);
+    // 同じクレートの別の版が混ざっていたら、impl が効かない原因になりやすいので知らせる
+    for crate_name in crates::conflicting_crates(doc, details.iter().map(|detail| detail.crate_id))
+    {
+        eprintln!(
+            "warning: results come from more than one version of crate `{}`; \
+             trait impls from one version don't apply to types of the other",
+            crate_name
+        );
+    }
+    for (i, detail) in details.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print!("{}", show::to_text(detail));
+    }
+    !details.is_empty()
+}
+
+fn print_features(doc: &RustDocJson) -> bool {
+    let features = features::collect_features(doc);
+    for (name, info) in &features {
+        match &info.description {
+            Some(description) => println!("{} — {}", name, description),
+            None => println!("{}", name),
+        }
+        for item in &info.items {
+            println!("    {}", item);
+        }
+    }
+    !features.is_empty()
+}
+
+fn print_no_std_report(doc: &RustDocJson) -> bool {
+    let reports = no_std::analyze(doc);
+    let count = |req: no_std::Requirement| reports.iter().filter(|r| r.requirement == req).count();
+
+    for report in reports
+        .iter()
+        .filter(|r| r.requirement != no_std::Requirement::Core)
+    {
+        println!(
+            "{:<5} {}: {}",
+            report.requirement.label(),
+            report.name,
+            report.offending_types.join(", ")
+        );
+    }
+
+    println!(
+        "Summary: {} core-only, {} need alloc, {} need std",
+        count(no_std::Requirement::Core),
+        count(no_std::Requirement::Alloc),
+        count(no_std::Requirement::Std)
+    );
+    let overall = reports
+        .iter()
+        .map(|r| r.requirement)
+        .max()
+        .unwrap_or(no_std::Requirement::Core);
+    println!("Public API requires: {}", overall.label());
+    !reports.is_empty()
+}
+
+fn print_panics_report(doc: &RustDocJson) -> bool {
+    let reports = panics::analyze(doc);
+    for report in &reports {
+        let reasons: Vec<&str> = report.reasons.iter().map(|r| r.label()).collect();
+        match report.excerpt.as_deref() {
+            Some(excerpt) if !excerpt.is_empty() => {
+                println!("{} [{}] {}", report.name, reasons.join(", "), excerpt)
+            }
+            _ => println!("{} [{}]", report.name, reasons.join(", ")),
+        }
+    }
+    !reports.is_empty()
+}
+
+fn print_stats(doc: &RustDocJson, top: usize) -> bool {
+    let functions = doc
+        .index
+        .values()
+        .filter(|item| item.crate_id == 0 && item.inner.function.is_some())
+        .count();
+    println!("Functions: {}", functions);
+
+    println!("Most referenced types:");
+    for usage in stats::most_referenced_types(doc).iter().take(top) {
+        println!("{:>6}  {}", usage.count, usage.path);
+    }
+    functions > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{render_signatures, RenderOptions, RustDocJson};
+    use crate::template::Template;
+
+    /// struct Foo と、その Display の impl と、他のクレートの blanket impl (From<T> for T)
+    fn doc_with_blanket_impl() -> RustDocJson {
+        let unit_fn = |name: &str, crate_id: u32| {
+            json!({
+                "name": name,
+                "crate_id": crate_id,
+                "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+            })
+        };
+        let foo = json!({ "resolved_path": { "path": "Foo", "id": 1, "args": null } });
+        serde_json::from_value(json!({
+            "root": 0,
+            "index": {
+                "0": { "name": "fx", "inner": { "module": { "is_crate": true, "items": [1] } } },
+                "1": {
+                    "name": "Foo",
+                    "inner": { "struct": { "kind": "unit" } }
+                },
+                "2": {
+                    "name": null,
+                    "inner": { "impl": { "trait": { "path": "Display", "id": 20, "args": null }, "for": foo, "items": [4] } }
+                },
+                "3": {
+                    "name": null,
+                    "crate_id": 1,
+                    "inner": { "impl": {
+                        "trait": { "path": "From", "id": 21, "args": null },
+                        "for": foo,
+                        "items": [5],
+                        "blanket_impl": { "generic": "T" }
+                    } }
+                },
+                "4": unit_fn("fmt", 0),
+                "5": unit_fn("from", 1)
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["fx"], "kind": "module" },
+                "1": { "crate_id": 0, "path": ["fx", "Foo"], "kind": "struct" }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn template_lists_methods_of_written_impls_only() {
+        let doc = doc_with_blanket_impl();
+        let template = Template::parse("{kind} {path}").unwrap();
+        let (out, found) =
+            render_signatures(&doc, Some(&template), false, RenderOptions::default());
+        assert!(found);
+        let mut lines: Vec<&str> = out.lines().collect();
+        lines.sort();
+        assert_eq!(lines, ["fn fx::Foo::fmt", "struct fx::Foo"]);
+    }
+
+    #[test]
+    fn listing_nests_methods_in_their_impl() {
+        let doc = doc_with_blanket_impl();
+        let (out, _) = render_signatures(&doc, None, false, RenderOptions::default());
+        assert!(!out.contains("fn from"));
+        assert_eq!(out.matches("fn fmt").count(), 1);
+    }
 }
@@ -0,0 +1,382 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Error as IoError;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+/// ----------------------------------------
+/// rustdoc JSON を共有できる形にする (ベンチマーク用の入力や不具合報告向け)
+/// 構造 (アイテム数、ID、型の形、impl の関係) はそのままで、
+///   - docs、リンク、deprecated の note、must_use の理由、crate_version、
+///     外部クレートのファイルのパスを消す
+///   - このクレートのアイテム名を "Item3" / "item_3" のような名前に置き換える
+///     (同じ名前は同じ名前になる)
+///   - 引数名を "arg0" などに、ソースのファイル名を "src/file0.rs" などに置き換える
+///   - 定数や static の値、判別式、配列の長さなどの式は数値のリテラル以外 "_" にし、
+///     マクロの本体は `{ ... }` にする
+///   - doc(hidden) 以外の #[doc(...)] 属性と cfg 属性 (フィーチャー名やソースの位置を含む) を消す
+///
+/// 他のクレート (std など) の名前やプリミティブ型の名前はそのまま残す
+/// 連結された入力はドキュメントごとに1行ずつ出す
+/// ----------------------------------------
+pub fn minify_file(path: &Path) -> Result<Vec<String>, IoError> {
+    let bytes = fs::read(path).map_err(|e| {
+        IoError::new(
+            e.kind(),
+            format!("failed to open file '{}': {}", path.display(), e),
+        )
+    })?;
+    let mut minifier = Minifier::default();
+    let mut documents = Vec::new();
+    for doc in serde_json::Deserializer::from_slice(&bytes).into_iter::<Value>() {
+        let mut doc = doc.map_err(IoError::other)?;
+        minifier.minify(&mut doc);
+        documents.push(serde_json::to_string(&doc).map_err(IoError::other)?);
+    }
+    Ok(documents)
+}
+
+/// 置き換えた名前の対応 (ドキュメントをまたいで同じ名前は同じ名前にする)
+#[derive(Default)]
+struct Minifier {
+    names: HashMap<String, String>,
+    files: HashMap<String, String>,
+}
+
+impl Minifier {
+    fn minify(&mut self, doc: &mut Value) {
+        if let Some(version) = doc.get_mut("crate_version") {
+            *version = Value::Null;
+        }
+        // rlib などの絶対パス (ホームディレクトリを含む)
+        if let Some(Value::Object(crates)) = doc.get_mut("external_crates") {
+            for info in crates.values_mut() {
+                if let Some(path) = info.get_mut("path") {
+                    *path = Value::String(String::new());
+                }
+            }
+        }
+
+        // このクレートのアイテムの ID (パスの名前を置き換えるかの判定に使う)
+        let mut local_ids = HashSet::new();
+        for key in ["index", "paths"] {
+            if let Some(Value::Object(map)) = doc.get(key) {
+                for (id, entry) in map {
+                    if entry.get("crate_id").and_then(Value::as_u64) == Some(0) {
+                        local_ids.insert(id.clone());
+                    }
+                }
+            }
+        }
+
+        // 他のクレートのトレイトの impl の中身 (fmt や add など) は名前を残す
+        let mut keep_names = HashSet::new();
+        if let Some(Value::Object(index)) = doc.get("index") {
+            for item in index.values() {
+                let Some(impl_) = item.get("inner").and_then(|inner| inner.get("impl")) else {
+                    continue;
+                };
+                let trait_id = impl_.get("trait").and_then(|trait_| trait_.get("id"));
+                if trait_id.is_some_and(|id| !local_ids.contains(&id_key(id))) {
+                    if let Some(Value::Array(items)) = impl_.get("items") {
+                        keep_names.extend(items.iter().map(id_key));
+                    }
+                }
+            }
+        }
+
+        if let Some(Value::Object(index)) = doc.get_mut("index") {
+            for (id, item) in index.iter_mut() {
+                self.minify_item(item, keep_names.contains(id));
+                if let Some(inner) = item.get_mut("inner") {
+                    self.rename_paths(inner, &local_ids);
+                }
+            }
+        }
+        if let Some(Value::Object(paths)) = doc.get_mut("paths") {
+            for summary in paths.values_mut() {
+                if summary.get("crate_id").and_then(Value::as_u64) != Some(0) {
+                    continue;
+                }
+                if let Some(Value::Array(segments)) = summary.get_mut("path") {
+                    for segment in segments {
+                        if let Value::String(name) = segment {
+                            *name = self.rename(name);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// アイテム1つ分 (keep_name なら名前は残す)
+    fn minify_item(&mut self, item: &mut Value, keep_name: bool) {
+        let Value::Object(item) = item else {
+            return;
+        };
+        item.insert("docs".to_string(), Value::Null);
+        item.insert("links".to_string(), Value::Object(Map::new()));
+        if let Some(Value::Array(attrs)) = item.get_mut("attrs") {
+            attrs.retain(|attr| !leaks_source(attr));
+            for attr in attrs.iter_mut() {
+                // { "must_use": { "reason": "..." } }
+                if let Some(Value::Object(must_use)) = attr.get_mut("must_use") {
+                    if must_use.contains_key("reason") {
+                        must_use.insert("reason".to_string(), Value::Null);
+                    }
+                }
+            }
+        }
+        if let Some(Value::Object(deprecation)) = item.get_mut("deprecation") {
+            if deprecation.contains_key("note") {
+                deprecation.insert("note".to_string(), Value::Null);
+            }
+        }
+        if let Some(Value::Object(span)) = item.get_mut("span") {
+            if let Some(Value::String(filename)) = span.get_mut("filename") {
+                let next = format!("src/file{}.rs", self.files.len());
+                *filename = self.files.entry(filename.clone()).or_insert(next).clone();
+            }
+        }
+
+        let is_local = item.get("crate_id").and_then(Value::as_u64) == Some(0);
+        let is_primitive = item
+            .get("inner")
+            .is_some_and(|inner| inner.get("primitive").is_some());
+        if is_local && !is_primitive && !keep_name {
+            if let Some(Value::String(name)) = item.get_mut("name") {
+                *name = self.rename(name);
+            }
+            // 再エクスポートの名前 (pub use ... as Name)
+            let use_name = item
+                .get_mut("inner")
+                .and_then(|inner| inner.get_mut("use"))
+                .and_then(|use_| use_.get_mut("name"));
+            if let Some(Value::String(name)) = use_name {
+                *name = self.rename(name);
+            }
+        }
+        // マクロはマッチャーの腕まで文字列で入っている
+        if let Some(name) = item.get("name").and_then(Value::as_str).map(str::to_string) {
+            if let Some(Value::String(macro_)) = item
+                .get_mut("inner")
+                .and_then(|inner| inner.get_mut("macro"))
+            {
+                *macro_ = format!("macro_rules! {} {{ ... }}", name);
+            }
+        }
+    }
+
+    /// アイテムの中身の、型の中のパス ("inner::Deep" など) と use の元のパスのうち、
+    /// このクレートのアイテムを指すものの名前を置き換える
+    /// 関数と関数ポインタの引数名もここで置き換える
+    fn rename_paths(&mut self, value: &mut Value, local_ids: &HashSet<String>) {
+        match value {
+            Value::Object(map) => {
+                // "inputs": [["name", type], ...] ("self" と名前のない "_" は残す)
+                if let Some(Value::Array(inputs)) = map.get_mut("inputs") {
+                    for (i, input) in inputs.iter_mut().enumerate() {
+                        if let Some(Value::String(name)) = input.get_mut(0) {
+                            if name != "self" && name != "_" {
+                                *name = format!("arg{}", i);
+                            }
+                        }
+                    }
+                }
+                // 関連型などのパス ("qualified_path": { "name": "Item", "trait": ..., "self_type": ... })
+                // トレイトがこのクレートのもの (または Self::Item のようにトレイトなし) なら、
+                // 関連アイテムと同じ名前に置き換える
+                if map.contains_key("self_type") {
+                    let local_trait = match map.get("trait") {
+                        None | Some(Value::Null) => true,
+                        Some(trait_) => trait_
+                            .get("id")
+                            .is_some_and(|id| local_ids.contains(&id_key(id))),
+                    };
+                    if local_trait {
+                        if let Some(Value::String(name)) = map.get_mut("name") {
+                            *name = self.rename(name);
+                        }
+                    }
+                }
+                let points_local = map
+                    .get("id")
+                    .is_some_and(|id| local_ids.contains(&id_key(id)));
+                if points_local {
+                    // このクレートのトレイトの関連型の制約 (Tr<Item = u8>)
+                    let constraints = map
+                        .get_mut("args")
+                        .and_then(|args| args.get_mut("angle_bracketed"))
+                        .and_then(|args| args.get_mut("constraints"));
+                    if let Some(Value::Array(constraints)) = constraints {
+                        for constraint in constraints {
+                            if let Some(Value::String(name)) = constraint.get_mut("name") {
+                                *name = self.rename(name);
+                            }
+                        }
+                    }
+                    for key in ["path", "source"] {
+                        if let Some(Value::String(path)) = map.get_mut(key) {
+                            *path = path
+                                .split("::")
+                                .map(|segment| self.rename(segment))
+                                .collect::<Vec<_>>()
+                                .join("::");
+                        }
+                    }
+                }
+                // 式: 定数 ("const": { "expr", "value" })、判別式、ジェネリクスの定数引数、
+                // static の "expr"、関連定数の "value"、配列の "len"
+                let value = map
+                    .get("value")
+                    .and_then(Value::as_str)
+                    .filter(|value| is_number_literal(value));
+                if let Some(value) = value.map(str::to_string) {
+                    if map.contains_key("expr") {
+                        map.insert("expr".to_string(), Value::String(value));
+                    }
+                } else {
+                    for key in ["expr", "value", "len"] {
+                        if let Some(Value::String(expr)) = map.get_mut(key) {
+                            if !is_number_literal(expr) {
+                                *expr = "_".to_string();
+                            }
+                        }
+                    }
+                    if map.contains_key("expr") && map.get("value").is_some_and(Value::is_string) {
+                        map.insert("value".to_string(), Value::Null);
+                    }
+                }
+                for child in map.values_mut() {
+                    self.rename_paths(child, local_ids);
+                }
+            }
+            Value::Array(values) => {
+                for child in values {
+                    self.rename_paths(child, local_ids);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 名前を置き換える (大文字で始まるものは "Item3"、それ以外は "item_3")
+    /// "crate" / "self" / "super" はそのまま
+    fn rename(&mut self, name: &str) -> String {
+        if matches!(name, "crate" | "self" | "super" | "") {
+            return name.to_string();
+        }
+        let next = self.names.len();
+        self.names
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                if name.starts_with(|c: char| c.is_uppercase()) {
+                    format!("Item{}", next)
+                } else {
+                    format!("item_{}", next)
+                }
+            })
+            .clone()
+    }
+}
+
+/// ID を index のキーの形にする (形式によって数値のものと文字列のものがある)
+fn id_key(id: &Value) -> String {
+    match id {
+        Value::String(id) => id.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// 識別子を含まない数値のリテラルか ("3", "-1", "10usize", "0x1f")
+fn is_number_literal(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// 消す属性: doc(hidden) 以外の doc 属性と cfg 属性
+/// 文字列 ("#[doc(alias = ...)]") のものと、新しい形式の { "other": "#[...]" } のものがある
+fn leaks_source(attr: &Value) -> bool {
+    let text = match attr {
+        Value::String(text) => text.as_str(),
+        Value::Object(map) => match map.get("other") {
+            Some(Value::String(text)) => text.as_str(),
+            _ => return false,
+        },
+        _ => return false,
+    };
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    (text.starts_with("#[doc") && text != "#[doc(hidden)]")
+        || text.starts_with("#[cfg")
+        || text.contains("CfgTrace")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{is_number_literal, Minifier};
+
+    #[test]
+    fn no_original_identifier_survives() {
+        let item = |name: &str, inner: serde_json::Value| {
+            json!({
+                "name": name,
+                "crate_id": 0,
+                "docs": "secret docs",
+                "span": { "filename": "src/secret_mod.rs", "begin": [1, 0], "end": [1, 1] },
+                "inner": inner
+            })
+        };
+        let mut doc = json!({
+            "root": 0,
+            "crate_version": "0.1.0-secret",
+            "index": {
+                "0": item("secret_crate", json!({ "module": { "is_crate": true, "items": [1, 2, 3, 4, 5, 6] } })),
+                "1": item("SECRET_MAX", json!({ "constant": {
+                    "type": { "primitive": "usize" },
+                    "const": { "expr": "SECRET_BASE * 2", "value": null, "is_literal": false }
+                } })),
+                "2": item("SECRET_NAME", json!({ "static": {
+                    "type": { "primitive": "str" }, "expr": "\"secret text\"", "is_mutable": false
+                } })),
+                "3": item("secret_mac", json!({ "macro": "macro_rules! secret_mac {\n    ($secret_arg:expr) => { ... };\n}" })),
+                "4": item("SecretEnum", json!({ "enum": { "variants": [5], "has_stripped_variants": false } })),
+                "5": item("SecretVariant", json!({ "variant": {
+                    "kind": "plain",
+                    "discriminant": { "expr": "SECRET_BASE + 1", "value": "5" }
+                } })),
+                "6": item("secret_fn", json!({ "function": { "sig": {
+                    "inputs": [["secret_arg", { "array": { "type": { "primitive": "u8" }, "len": "SECRET_MAX" } }]],
+                    "output": null
+                } } }))
+            },
+            "paths": {
+                "4": { "crate_id": 0, "path": ["secret_crate", "SecretEnum"], "kind": "enum" }
+            }
+        });
+        Minifier::default().minify(&mut doc);
+        let out = doc.to_string().to_lowercase();
+        assert!(!out.contains("secret"), "{}", out);
+        // 数値の判別式は残る
+        assert_eq!(
+            doc["index"]["5"]["inner"]["variant"]["discriminant"]["expr"],
+            "5"
+        );
+    }
+
+    #[test]
+    fn number_literals_are_recognized() {
+        assert!(is_number_literal("3"));
+        assert!(is_number_literal("-1"));
+        assert!(is_number_literal("10usize"));
+        assert!(is_number_literal("0x1f"));
+        assert!(!is_number_literal("N"));
+        assert!(!is_number_literal("MAX + 1"));
+        assert!(!is_number_literal("\"x\""));
+    }
+}
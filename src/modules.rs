@@ -0,0 +1,278 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::rustdoc_json::{attribute_to_string, keyword_name, Item, RustDocJson};
+use crate::signature_builder::Type;
+
+/// ----------------------------------------
+/// モジュールの木 (ルートはクレート)
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct ModuleTree {
+    /// 完全パス (例: "sample::inner")
+    pub path: String,
+    /// モジュールのドキュメント (//! ...)
+    pub docs: Option<String>,
+    /// 直下のアイテムのID (子モジュールも含む)
+    pub items: Vec<String>,
+    pub children: Vec<ModuleTree>,
+}
+
+/// ----------------------------------------
+/// ルートモジュールから items をたどって木を作る
+/// 連結された入力ではドキュメントごとに1本ずつ
+/// ----------------------------------------
+pub fn module_trees(doc: &RustDocJson) -> Vec<ModuleTree> {
+    let root_ids: Vec<String> = if doc.roots.is_empty() {
+        doc.index
+            .iter()
+            .filter(|(_, item)| item.inner.module.as_ref().is_some_and(|m| m.is_crate))
+            .map(|(id, _)| id.clone())
+            .collect()
+    } else {
+        doc.roots.iter().map(|root| root.0.clone()).collect()
+    };
+    root_ids
+        .iter()
+        .filter_map(|id| doc.index.get(id))
+        .map(|root| build(doc, root, root.name.clone().unwrap_or_default()))
+        .collect()
+}
+
+fn build(doc: &RustDocJson, item: &Item, path: String) -> ModuleTree {
+    let items: Vec<String> = item
+        .inner
+        .module
+        .as_ref()
+        .map(|module| module.items.iter().map(|id| id.0.clone()).collect())
+        .unwrap_or_default();
+    let children = items
+        .iter()
+        .filter_map(|id| {
+            let child = doc.index.get(id)?;
+            // キーワードやプリミティブのドキュメント用のモジュールは除く
+            if child.inner.module.is_none() || is_doc_only_module(child) {
+                return None;
+            }
+            let name = child.name.as_deref()?;
+            Some(build(doc, child, format!("{}::{}", path, name)))
+        })
+        .collect();
+    ModuleTree {
+        path,
+        docs: item.docs.clone(),
+        items,
+        children,
+    }
+}
+
+/// #[doc(keyword = "...")] / #[rustc_doc_primitive = "..."] 付きのモジュールか
+fn is_doc_only_module(item: &Item) -> bool {
+    keyword_name(item).is_some()
+        || item.attrs.iter().any(|attr| {
+            attribute_to_string(attr)
+                .to_lowercase()
+                .replace('_', "")
+                .contains("docprimitive")
+        })
+}
+
+/// ----------------------------------------
+/// このクレートのモジュール: 完全パス -> モジュールのドキュメント (//! ...)
+/// ----------------------------------------
+pub fn crate_modules(doc: &RustDocJson) -> BTreeMap<String, Option<String>> {
+    fn walk(tree: &ModuleTree, out: &mut BTreeMap<String, Option<String>>) {
+        out.insert(tree.path.clone(), tree.docs.clone());
+        for child in &tree.children {
+            walk(child, out);
+        }
+    }
+    let mut modules = BTreeMap::new();
+    for tree in module_trees(doc) {
+        walk(&tree, &mut modules);
+    }
+    modules
+}
+
+/// ----------------------------------------
+/// アイテムID -> それが属するモジュールの完全パス
+/// モジュール直下のアイテムに加えて、
+/// トレイトの中身はトレイトの、impl とその中身は対象の型のモジュールに入れる
+/// (プリミティブの impl はプリミティブのドキュメントがあるモジュール。
+///  他クレートのアイテムは複数の型で共有されるので入れない)
+/// ----------------------------------------
+pub fn item_modules(doc: &RustDocJson) -> HashMap<String, String> {
+    fn walk(tree: &ModuleTree, out: &mut HashMap<String, String>) {
+        for id in &tree.items {
+            out.insert(id.clone(), tree.path.clone());
+        }
+        for child in &tree.children {
+            walk(child, out);
+        }
+    }
+    let mut modules = HashMap::new();
+    for tree in module_trees(doc) {
+        walk(&tree, &mut modules);
+    }
+
+    // プリミティブの名前 -> そのドキュメントがあるモジュール (`impl u32` の置き場所)
+    let primitive_modules: HashMap<&str, String> = doc
+        .index
+        .iter()
+        .filter_map(|(id, item)| {
+            let primitive = item.inner.primitive.as_ref()?;
+            Some((primitive.name.as_str(), modules.get(id)?.clone()))
+        })
+        .collect();
+
+    let mut owned = Vec::new();
+    for (id, item) in &doc.index {
+        if let Some(trait_) = &item.inner.trait_ {
+            if let Some(module) = modules.get(id) {
+                owned.extend(
+                    trait_
+                        .items
+                        .iter()
+                        .map(|child| (child.0.clone(), module.clone())),
+                );
+            }
+        }
+        let Some(imp) = &item.inner.impl_ else {
+            continue;
+        };
+        let module = match &imp.for_ {
+            Type::ResolvedPath { resolved_path } => resolved_path
+                .id
+                .as_ref()
+                .and_then(|self_id| modules.get(&self_id.0)),
+            Type::Primitive { primitive } => primitive_modules.get(primitive.as_str()),
+            _ => None,
+        };
+        let Some(module) = module else {
+            continue;
+        };
+        owned.push((id.clone(), module.clone()));
+        owned.extend(
+            imp.items
+                .iter()
+                .map(|child| (child.0.clone(), module.clone())),
+        );
+    }
+    for (id, module) in owned {
+        let is_local = doc.index.get(&id).is_some_and(|item| item.crate_id == 0);
+        if is_local {
+            modules.entry(id).or_insert(module);
+        }
+    }
+    modules
+}
+
+/// ----------------------------------------
+/// use による再エクスポート
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct Reexport {
+    /// 再エクスポートされた先のパス (例: "sample::Deep")
+    pub path: String,
+    /// 元のアイテムのID
+    pub target: String,
+}
+
+/// ----------------------------------------
+/// use 宣言からたどれる再エクスポートを集める
+/// glob (`pub use inner::*;`) はこのクレートのモジュールなら中身を展開する
+/// ----------------------------------------
+pub fn reexports(doc: &RustDocJson) -> Vec<Reexport> {
+    fn walk(doc: &RustDocJson, tree: &ModuleTree, out: &mut Vec<Reexport>) {
+        for id in &tree.items {
+            let Some(use_) = doc.index.get(id).and_then(|item| item.inner.use_.as_ref()) else {
+                continue;
+            };
+            let Some(target) = &use_.id else {
+                continue;
+            };
+            if !use_.is_glob {
+                out.push(Reexport {
+                    path: format!("{}::{}", tree.path, use_.name),
+                    target: target.0.clone(),
+                });
+                continue;
+            }
+            let Some(module) = doc
+                .index
+                .get(&target.0)
+                .and_then(|item| item.inner.module.as_ref())
+            else {
+                continue;
+            };
+            for child in &module.items {
+                if let Some(name) = doc.index.get(&child.0).and_then(|item| item.name.as_ref()) {
+                    out.push(Reexport {
+                        path: format!("{}::{}", tree.path, name),
+                        target: child.0.clone(),
+                    });
+                }
+            }
+        }
+        for child in &tree.children {
+            walk(doc, child, out);
+        }
+    }
+    let mut reexports = Vec::new();
+    for tree in module_trees(doc) {
+        walk(doc, &tree, &mut reexports);
+    }
+    reexports
+}
+
+/// ----------------------------------------
+/// use 宣言のID -> 宣言が置かれた場所のパス ("sample::Deep", "sample::*")
+/// ----------------------------------------
+pub fn use_paths(doc: &RustDocJson) -> HashMap<String, String> {
+    fn walk(doc: &RustDocJson, tree: &ModuleTree, out: &mut HashMap<String, String>) {
+        for id in &tree.items {
+            if let Some(use_) = doc.index.get(id).and_then(|item| item.inner.use_.as_ref()) {
+                let name = if use_.is_glob {
+                    "*"
+                } else {
+                    use_.name.as_str()
+                };
+                out.insert(id.clone(), format!("{}::{}", tree.path, name));
+            }
+        }
+        for child in &tree.children {
+            walk(doc, child, out);
+        }
+    }
+    let mut paths = HashMap::new();
+    for tree in module_trees(doc) {
+        walk(doc, &tree, &mut paths);
+    }
+    paths
+}
+
+/// ----------------------------------------
+/// アイテムID -> use で取り込むときのパス
+/// 定義された場所のパスと再エクスポート先のパスのうち、
+/// いちばん短いもの (セグメント数、文字数の順に比べる) を選ぶ
+/// ----------------------------------------
+pub fn import_paths(doc: &RustDocJson) -> HashMap<String, String> {
+    let mut paths: HashMap<String, String> = doc
+        .paths
+        .iter()
+        .map(|(id, summary)| (id.clone(), summary.path.join("::")))
+        .collect();
+    for reexport in reexports(doc) {
+        let is_shorter = match paths.get(&reexport.target) {
+            Some(current) => path_len(&reexport.path) < path_len(current),
+            None => true,
+        };
+        if is_shorter {
+            paths.insert(reexport.target, reexport.path);
+        }
+    }
+    paths
+}
+
+fn path_len(path: &str) -> (usize, usize) {
+    (path.matches("::").count(), path.len())
+}
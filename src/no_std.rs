@@ -0,0 +1,221 @@
+use crate::rustdoc_json::RustDocJson;
+use crate::signature_builder::{visit_generics_paths, visit_type_paths, ResolvedPath};
+
+/// ----------------------------------------
+/// アイテムが必要とする標準ライブラリの層
+/// (core < alloc < std の順に重い)
+/// ----------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Requirement {
+    Core,
+    Alloc,
+    Std,
+}
+
+impl Requirement {
+    pub fn label(self) -> &'static str {
+        match self {
+            Requirement::Core => "core",
+            Requirement::Alloc => "alloc",
+            Requirement::Std => "std",
+        }
+    }
+}
+
+/// ----------------------------------------
+/// 1アイテム分の判定結果
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct ItemReport {
+    pub name: String,
+    pub requirement: Requirement,
+    /// alloc / std 由来の型やトレイト (完全パス)
+    pub offending_types: Vec<String>,
+}
+
+/// ----------------------------------------
+/// 関数シグネチャに出てくる型とトレイトから、各アイテムが
+/// core / alloc / std のどれを必要とするかを判定する
+/// 結果は重いもの順・名前順
+/// ----------------------------------------
+pub fn analyze(doc: &RustDocJson) -> Vec<ItemReport> {
+    let mut reports = Vec::new();
+
+    for item in doc.index.values() {
+        if item.crate_id != 0 {
+            continue;
+        }
+        let Some(func) = &item.inner.function else {
+            continue;
+        };
+        let mut requirement = Requirement::Core;
+        let mut offending_types = Vec::new();
+
+        let mut check = |resolved_path: &ResolvedPath| {
+            // paths テーブルで定義元のクレートを引く。なければ書かれたパスで推測
+            let summary = resolved_path
+                .id
+                .as_ref()
+                .and_then(|id| doc.paths.get(&id.0));
+            let (full_path, ty_requirement) = match summary {
+                Some(summary) => {
+                    let requirement = match summary.path.first().map(String::as_str) {
+                        Some("std") => Requirement::Std,
+                        Some("alloc") => Requirement::Alloc,
+                        _ => Requirement::Core,
+                    };
+                    (summary.path.join("::"), requirement)
+                }
+                None => (
+                    resolved_path.name.clone(),
+                    requirement_from_written_path(&resolved_path.name),
+                ),
+            };
+            if ty_requirement == Requirement::Core {
+                return;
+            }
+            requirement = requirement.max(ty_requirement);
+            if !offending_types.contains(&full_path) {
+                offending_types.push(full_path);
+            }
+        };
+        // 引数・戻り値の型と、`impl Read` / `dyn Write` / `T: Read` などの境界のトレイト
+        for (_, ty) in &func.sig.inputs {
+            visit_type_paths(ty, &mut check);
+        }
+        if let Some(output) = &func.sig.output {
+            visit_type_paths(output, &mut check);
+        }
+        visit_generics_paths(&func.generics, &mut check);
+
+        reports.push(ItemReport {
+            name: item.name.clone().unwrap_or_else(|| "unknown".to_string()),
+            requirement,
+            offending_types,
+        });
+    }
+
+    reports.sort_by(|a, b| {
+        b.requirement
+            .cmp(&a.requirement)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    reports
+}
+
+/// std にしかないモジュール
+const STD_ONLY_MODULES: &[&str] = &["env", "fs", "io", "net", "os", "path", "process", "thread"];
+/// std から再エクスポートされている alloc のモジュール
+const ALLOC_MODULES: &[&str] = &["borrow", "boxed", "collections", "rc", "string", "vec"];
+/// prelude から使える alloc の型
+const ALLOC_PRELUDE: &[&str] = &["Box", "String", "ToOwned", "ToString", "Vec"];
+
+/// ----------------------------------------
+/// ID がない (ソースから読んだ) 場合に、書かれたパスから推測する
+/// std::fmt のように core の再エクスポートであるものは core 扱い
+/// ----------------------------------------
+fn requirement_from_written_path(path: &str) -> Requirement {
+    let segments: Vec<&str> = path.split("::").collect();
+    match segments.as_slice() {
+        ["std", "collections", name, ..] if name.starts_with("Hash") => Requirement::Std,
+        ["std", "sync", "Arc" | "Weak"] => Requirement::Alloc,
+        ["std", "sync", ..] => Requirement::Std,
+        ["std", module, ..] if STD_ONLY_MODULES.contains(module) => Requirement::Std,
+        ["std" | "alloc", module, ..] if ALLOC_MODULES.contains(module) => Requirement::Alloc,
+        ["alloc", ..] => Requirement::Alloc,
+        [name] if ALLOC_PRELUDE.contains(name) => Requirement::Alloc,
+        _ => Requirement::Core,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::{analyze, Requirement};
+    use crate::rustdoc_json::RustDocJson;
+
+    fn trait_bound(path: &str, id: u32) -> Value {
+        json!({ "trait_bound": { "trait": { "path": path, "id": id, "args": null }, "modifier": "none" } })
+    }
+
+    fn function(name: &str, inputs: Value, generics: Value) -> Value {
+        json!({
+            "name": name,
+            "inner": { "function": { "sig": { "inputs": inputs, "output": null }, "generics": generics } }
+        })
+    }
+
+    fn requirements(index: Value) -> Vec<(String, Requirement, Vec<String>)> {
+        let doc: RustDocJson = serde_json::from_value(json!({
+            "index": index,
+            "paths": {
+                "10": { "crate_id": 1, "path": ["std", "io", "Read"], "kind": "trait" },
+                "11": { "crate_id": 1, "path": ["std", "io", "Write"], "kind": "trait" },
+                "12": { "crate_id": 2, "path": ["core", "clone", "Clone"], "kind": "trait" }
+            }
+        }))
+        .unwrap();
+        analyze(&doc)
+            .into_iter()
+            .map(|report| (report.name, report.requirement, report.offending_types))
+            .collect()
+    }
+
+    #[test]
+    fn impl_trait_and_dyn_trait_bounds_count() {
+        let dyn_write = json!({ "dyn_trait": { "traits": [{ "trait": { "path": "std::io::Write", "id": 11, "args": null } }], "lifetime": null } });
+        let index = json!({
+            "0": function("read_all", json!([["r", { "impl_trait": [trait_bound("std::io::Read", 10)] }]]), json!({})),
+            "1": function("write_all", json!([["w", { "borrowed_ref": { "lifetime": null, "is_mutable": true, "type": dyn_write } }]]), json!({}))
+        });
+        assert_eq!(
+            requirements(index),
+            [
+                (
+                    "read_all".to_string(),
+                    Requirement::Std,
+                    vec!["std::io::Read".to_string()]
+                ),
+                (
+                    "write_all".to_string(),
+                    Requirement::Std,
+                    vec!["std::io::Write".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn generic_param_bounds_and_where_clauses_count() {
+        let r = json!({ "generic": "R" });
+        let index = json!({
+            "0": function("param_bound", json!([["r", r]]), json!({ "params": [
+                { "name": "R", "kind": { "type": { "bounds": [trait_bound("Read", 10)], "default": null, "is_synthetic": false } } }
+            ] })),
+            "1": function("where_bound", json!([["r", r]]), json!({
+                "params": [{ "name": "R", "kind": { "type": { "bounds": [], "default": null, "is_synthetic": false } } }],
+                "where_predicates": [{ "bound_predicate": { "type": r, "bounds": [trait_bound("Write", 11)] } }]
+            })),
+            "2": function("core_only", json!([["r", r]]), json!({ "params": [
+                { "name": "R", "kind": { "type": { "bounds": [trait_bound("Clone", 12)], "default": null, "is_synthetic": false } } }
+            ] }))
+        });
+        assert_eq!(
+            requirements(index),
+            [
+                (
+                    "param_bound".to_string(),
+                    Requirement::Std,
+                    vec!["std::io::Read".to_string()]
+                ),
+                (
+                    "where_bound".to_string(),
+                    Requirement::Std,
+                    vec!["std::io::Write".to_string()]
+                ),
+                ("core_only".to_string(), Requirement::Core, vec![]),
+            ]
+        );
+    }
+}
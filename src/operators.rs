@@ -0,0 +1,106 @@
+/// ----------------------------------------
+/// 演算子と、それを実装するトレイトのメソッドの対応
+/// (演算子, トレイトの公開パス, メソッド名)
+/// 1つの演算子に複数のトレイトが対応するものもある (`[]` は Index と IndexMut)
+/// `!=` は ne のデフォルト実装が eq を呼ぶので、実装されるのは eq
+/// ----------------------------------------
+const OPERATORS: &[(&str, &str, &str)] = &[
+    ("+", "core::ops::Add", "add"),
+    ("-", "core::ops::Sub", "sub"),
+    ("*", "core::ops::Mul", "mul"),
+    ("/", "core::ops::Div", "div"),
+    ("%", "core::ops::Rem", "rem"),
+    ("&", "core::ops::BitAnd", "bitand"),
+    ("|", "core::ops::BitOr", "bitor"),
+    ("^", "core::ops::BitXor", "bitxor"),
+    ("<<", "core::ops::Shl", "shl"),
+    (">>", "core::ops::Shr", "shr"),
+    ("+=", "core::ops::AddAssign", "add_assign"),
+    ("-=", "core::ops::SubAssign", "sub_assign"),
+    ("*=", "core::ops::MulAssign", "mul_assign"),
+    ("/=", "core::ops::DivAssign", "div_assign"),
+    ("%=", "core::ops::RemAssign", "rem_assign"),
+    ("&=", "core::ops::BitAndAssign", "bitand_assign"),
+    ("|=", "core::ops::BitOrAssign", "bitor_assign"),
+    ("^=", "core::ops::BitXorAssign", "bitxor_assign"),
+    ("<<=", "core::ops::ShlAssign", "shl_assign"),
+    (">>=", "core::ops::ShrAssign", "shr_assign"),
+    ("!", "core::ops::Not", "not"),
+    ("-neg", "core::ops::Neg", "neg"),
+    ("*deref", "core::ops::Deref", "deref"),
+    ("*deref", "core::ops::DerefMut", "deref_mut"),
+    ("[]", "core::ops::Index", "index"),
+    ("[]", "core::ops::IndexMut", "index_mut"),
+    ("==", "core::cmp::PartialEq", "eq"),
+    ("!=", "core::cmp::PartialEq", "eq"),
+    ("<", "core::cmp::PartialOrd", "partial_cmp"),
+    (">", "core::cmp::PartialOrd", "partial_cmp"),
+    ("<=", "core::cmp::PartialOrd", "partial_cmp"),
+    (">=", "core::cmp::PartialOrd", "partial_cmp"),
+];
+
+/// ----------------------------------------
+/// 演算子を (トレイトの公開パス, メソッド名) の一覧にする
+/// 演算子でなければ空
+/// 例: "+" -> [("core::ops::Add", "add")],
+///     "[]" -> [("core::ops::Index", "index"), ("core::ops::IndexMut", "index_mut")]
+/// ----------------------------------------
+pub fn operator_methods(query: &str) -> Vec<(&'static str, &'static str)> {
+    OPERATORS
+        .iter()
+        .filter(|(operator, _, _)| *operator == query)
+        .map(|(_, trait_, method)| (*trait_, *method))
+        .collect()
+}
+
+/// ----------------------------------------
+/// paths テーブルのトレイトのパスが、演算子のトレイトの公開パスを指しているか
+/// paths テーブルでは `core::ops::arith::Add` のように非公開のモジュールを含むので、
+/// クレートと最上位のモジュールとトレイト名で比べる
+/// 例: ("core::ops::arith::Add", "core::ops::Add") -> true,
+///     ("mycrate::ops::Add", "core::ops::Add") -> false
+/// ----------------------------------------
+pub fn is_operator_trait(trait_path: &str, wanted: &str) -> bool {
+    let segments: Vec<&str> = trait_path.split("::").collect();
+    let wanted: Vec<&str> = wanted.split("::").collect();
+    match (segments.as_slice(), wanted.as_slice()) {
+        ([krate, module, .., name], [wanted_krate, wanted_module, wanted_name]) => {
+            krate == wanted_krate && module == wanted_module && name == wanted_name
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_operator_trait, operator_methods};
+
+    #[test]
+    fn not_equal_is_implemented_by_eq() {
+        assert_eq!(operator_methods("!="), [("core::cmp::PartialEq", "eq")]);
+    }
+
+    #[test]
+    fn index_maps_to_both_traits() {
+        assert_eq!(
+            operator_methods("[]"),
+            [
+                ("core::ops::Index", "index"),
+                ("core::ops::IndexMut", "index_mut")
+            ]
+        );
+        assert!(operator_methods("add").is_empty());
+    }
+
+    #[test]
+    fn trait_paths_are_compared_beyond_the_last_segment() {
+        assert!(is_operator_trait("core::ops::arith::Add", "core::ops::Add"));
+        assert!(is_operator_trait(
+            "core::cmp::PartialEq",
+            "core::cmp::PartialEq"
+        ));
+        assert!(!is_operator_trait("mycrate::ops::Add", "core::ops::Add"));
+        assert!(!is_operator_trait("mycrate::Add", "core::ops::Add"));
+        assert!(!is_operator_trait("Add", "core::ops::Add"));
+    }
+}
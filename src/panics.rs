@@ -0,0 +1,108 @@
+use crate::rustdoc_json::RustDocJson;
+
+/// 抜粋の最大文字数
+const EXCERPT_LEN: usize = 120;
+
+/// ----------------------------------------
+/// なぜ panic しうると判断したか
+/// ----------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanicReason {
+    /// ドキュメントに "# Panics" 節がある
+    DocumentedPanics,
+    /// unwrap_* / expect_* という名前
+    Name,
+}
+
+impl PanicReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            PanicReason::DocumentedPanics => "# Panics",
+            PanicReason::Name => "name",
+        }
+    }
+}
+
+/// ----------------------------------------
+/// panic しうる関数1つ分
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct PanicReport {
+    pub name: String,
+    pub reasons: Vec<PanicReason>,
+    /// "# Panics" 節の抜粋
+    pub excerpt: Option<String>,
+}
+
+/// ----------------------------------------
+/// ドキュメントと関数名から panic しうる関数を洗い出す (名前順)
+/// ----------------------------------------
+pub fn analyze(doc: &RustDocJson) -> Vec<PanicReport> {
+    let mut reports = Vec::new();
+
+    for item in doc.index.values() {
+        if item.crate_id != 0 || item.inner.function.is_none() {
+            continue;
+        }
+        let name = item.name.clone().unwrap_or_else(|| "unknown".to_string());
+
+        let mut reasons = Vec::new();
+        let excerpt = item.docs.as_deref().and_then(panics_section);
+        if excerpt.is_some() {
+            reasons.push(PanicReason::DocumentedPanics);
+        }
+        if is_panicking_name(&name) {
+            reasons.push(PanicReason::Name);
+        }
+        if reasons.is_empty() {
+            continue;
+        }
+
+        reports.push(PanicReport {
+            name,
+            reasons,
+            excerpt,
+        });
+    }
+
+    reports.sort_by(|a, b| a.name.cmp(&b.name));
+    reports
+}
+
+/// unwrap / expect 系の名前か
+/// (unwrap_or* や unwrap_unchecked は panic しないので除く)
+fn is_panicking_name(name: &str) -> bool {
+    if name.starts_with("unwrap_or") || name == "unwrap_unchecked" {
+        return false;
+    }
+    name == "unwrap"
+        || name == "expect"
+        || name.starts_with("unwrap_")
+        || name.starts_with("expect_")
+}
+
+/// ----------------------------------------
+/// "# Panics" 節の本文を1行に詰めて返す
+/// 本文が空でも節があれば Some("") を返す
+/// ----------------------------------------
+fn panics_section(docs: &str) -> Option<String> {
+    let mut lines = docs.lines();
+    lines.find(|line| {
+        let trimmed = line.trim();
+        trimmed.starts_with('#') && trimmed.trim_start_matches('#').trim() == "Panics"
+    })?;
+
+    let body: Vec<&str> = lines
+        .take_while(|line| !line.trim_start().starts_with('#'))
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let text = body.join(" ");
+
+    if text.chars().count() > EXCERPT_LEN {
+        let cut: String = text.chars().take(EXCERPT_LEN).collect();
+        Some(format!("{}...", cut))
+    } else {
+        Some(text)
+    }
+}
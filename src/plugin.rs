@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Error as IoError, ErrorKind, Write};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+
+use serde::Serialize;
+
+use crate::rustdoc_json::{item_paths, item_to_signature_string, RustDocJson};
+use crate::signature_builder::RenderOptions;
+
+/// ----------------------------------------
+/// プラグインに渡すアイテムの情報
+/// ----------------------------------------
+#[derive(Debug, Serialize)]
+pub struct PluginItem {
+    pub id: String,
+    pub name: String,
+    /// 完全パス (分からなければ名前)
+    pub path: String,
+    /// 関数ならシグネチャ文字列
+    pub sig: Option<String>,
+    pub docs: Option<String>,
+}
+
+/// ----------------------------------------
+/// アイテムを絞り込むプラグイン
+/// 残すアイテムのIDを返す
+/// ----------------------------------------
+pub trait FilterPlugin {
+    fn keep(&mut self, items: &[PluginItem]) -> Result<HashSet<String>, IoError>;
+}
+
+/// ----------------------------------------
+/// 外部コマンドによるフィルタ
+/// stdin に PluginItem を1行1つの JSON (JSON Lines) で流し、
+/// stdout から残すアイテムのIDを1行1つで受け取る
+/// ----------------------------------------
+pub struct ExternalFilter {
+    /// 実行するコマンド (引数も書ける。split_command の規則で分ける)
+    pub command: String,
+}
+
+impl FilterPlugin for ExternalFilter {
+    fn keep(&mut self, items: &[PluginItem]) -> Result<HashSet<String>, IoError> {
+        let mut child = spawn_plugin(&self.command, Stdio::piped())?;
+
+        // 出力を読みながら書けるよう、書き込みは別スレッドで行う
+        let mut lines = Vec::with_capacity(items.len());
+        for item in items {
+            lines.push(serde_json::to_string(item).map_err(IoError::other)?);
+        }
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| IoError::other(format!("plugin '{}' has no stdin", self.command)))?;
+        let writer = thread::spawn(move || -> Result<(), IoError> {
+            for line in lines {
+                writeln!(stdin, "{}", line)?;
+            }
+            Ok(())
+        });
+
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| IoError::other(format!("plugin '{}' has no stdout", self.command)))?;
+        let mut kept = HashSet::new();
+        for line in BufReader::new(stdout).lines() {
+            let line = line?;
+            let id = line.trim();
+            if !id.is_empty() {
+                kept.insert(id.to_string());
+            }
+        }
+
+        // プラグインが途中で stdin を閉じても、読めた分は有効とする
+        let _ = writer.join();
+        let status = child.wait()?;
+        if !status.success() {
+            return Err(IoError::other(format!(
+                "plugin '{}' exited with {}",
+                self.command, status
+            )));
+        }
+        Ok(kept)
+    }
+}
+
+/// ----------------------------------------
+/// プラグインのコマンドを起動する (stdin はパイプ、stdout は指定どおり)
+/// ----------------------------------------
+pub fn spawn_plugin(command: &str, stdout: Stdio) -> Result<Child, IoError> {
+    let words = split_command(command)?;
+    let (program, args) = words
+        .split_first()
+        .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "empty plugin command"))?;
+    Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(stdout)
+        .spawn()
+        .map_err(|e| {
+            IoError::new(
+                e.kind(),
+                format!("failed to run plugin '{}': {}", command, e),
+            )
+        })
+}
+
+/// ----------------------------------------
+/// コマンド文字列を引数に分ける (シェルの簡易版)
+/// 空白で区切り、'...' の中はそのまま、"..." の中と外では \ で次の1文字をそのまま使う
+/// (`grep -v "a b"` は ["grep", "-v", "a b"])
+/// ----------------------------------------
+pub fn split_command(command: &str) -> Result<Vec<String>, IoError> {
+    let mut words = Vec::new();
+    // 引数の途中か ('' のような空の引数も1つと数える)
+    let mut word: Option<String> = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let current = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err(unclosed_quote(command)),
+                    }
+                }
+            }
+            '"' => {
+                let current = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => current.extend(chars.next()),
+                        Some(c) => current.push(c),
+                        None => return Err(unclosed_quote(command)),
+                    }
+                }
+            }
+            '\\' => word.get_or_insert_with(String::new).extend(chars.next()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+fn unclosed_quote(command: &str) -> IoError {
+    IoError::new(
+        ErrorKind::InvalidInput,
+        format!("unclosed quote in plugin command '{}'", command),
+    )
+}
+
+/// ----------------------------------------
+/// プラグインで index を絞り込む
+/// 名前のあるアイテムだけを渡し、名前のないもの (impl など) は残す
+/// ----------------------------------------
+pub fn apply_filter(
+    doc: &mut RustDocJson,
+    plugin: &mut dyn FilterPlugin,
+    options: RenderOptions,
+) -> Result<(), IoError> {
+    let paths = item_paths(doc);
+    let items: Vec<PluginItem> = doc
+        .index
+        .iter()
+        .filter_map(|(id, item)| {
+            let name = item.name.clone()?;
+            Some(PluginItem {
+                id: id.clone(),
+                path: paths.get(id).cloned().unwrap_or_else(|| name.clone()),
+                name,
+                sig: item_to_signature_string(item, options),
+                docs: item.docs.clone(),
+            })
+        })
+        .collect();
+
+    let kept = plugin.keep(&items)?;
+    let dropped: HashSet<String> = items
+        .into_iter()
+        .map(|item| item.id)
+        .filter(|id| !kept.contains(id))
+        .collect();
+    doc.index.retain(|id, _| !dropped.contains(id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_command;
+
+    #[test]
+    fn splits_on_whitespace() {
+        assert_eq!(
+            split_command("  grep  -v foo ").unwrap(),
+            ["grep", "-v", "foo"]
+        );
+        assert!(split_command("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn quotes_keep_spaces_together() {
+        assert_eq!(
+            split_command(r#"jq -r 'select(.name == "new")' "a b""#).unwrap(),
+            ["jq", "-r", r#"select(.name == "new")"#, "a b"]
+        );
+        assert_eq!(split_command("echo '' x").unwrap(), ["echo", "", "x"]);
+    }
+
+    #[test]
+    fn backslash_escapes_the_next_char() {
+        assert_eq!(split_command(r#"a\ b "c\"d""#).unwrap(), ["a b", r#"c"d"#]);
+    }
+
+    #[test]
+    fn unclosed_quote_is_an_error() {
+        assert!(split_command("grep 'oops").is_err());
+        assert!(split_command("grep \"oops").is_err());
+    }
+}
@@ -1,7 +1,13 @@
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
-use crate::signature_builder::{function_sig_to_string, FunctionSig};
+use crate::signature_builder::{
+    bounds_to_string, function_sig_to_string, generic_params_to_string, render_ident, render_path,
+    resolved_path_to_string, type_to_string, where_clause_to_string, FunctionSig, GenericBound,
+    Generics, RenderOptions, ResolvedPath, Type,
+};
 
 /// ----------------------------------------
 /// Rustdoc JSON のトップレベル
@@ -10,6 +16,271 @@ use crate::signature_builder::{function_sig_to_string, FunctionSig};
 pub struct RustDocJson {
     /// "index" フィールド: ID文字列 -> Item
     pub index: HashMap<String, Item>,
+
+    /// クレートルート (モジュール) のID
+    /// 連結された入力ではドキュメントごとのルートが順に並ぶ
+    #[serde(default, rename = "root", deserialize_with = "deserialize_root")]
+    pub roots: Vec<Id>,
+
+    /// "paths" フィールド: ID文字列 -> 完全パスなどの要約
+    /// (外部クレートのアイテムもここに入る)
+    #[serde(default)]
+    pub paths: HashMap<String, ItemSummary>,
+
+    /// "external_crates" フィールド: crate_id -> クレートの情報
+    #[serde(default)]
+    pub external_crates: HashMap<String, ExternalCrateInfo>,
+}
+
+/// ----------------------------------------
+/// 依存クレート1つ分
+/// 同じ名前で版の違うクレートは別の crate_id で入ってくる
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct ExternalCrateInfo {
+    pub name: String,
+    /// ドキュメントの URL (docs.rs なら版が入っている)
+    #[serde(default)]
+    pub html_root_url: Option<String>,
+}
+
+/// ----------------------------------------
+/// paths テーブルの1エントリ
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct ItemSummary {
+    /// 0 ならこのクレート、それ以外は external_crates のキー
+    pub crate_id: u32,
+    /// 例: ["std", "io", "Read"]
+    pub path: Vec<String>,
+    /// "struct", "enum", "trait" など
+    pub kind: String,
+}
+
+/// ----------------------------------------
+/// 型の完全パスを返す
+/// paths テーブルで引けなければ書かれたとおりの名前
+/// ----------------------------------------
+pub fn resolved_path_string(doc: &RustDocJson, resolved_path: &ResolvedPath) -> String {
+    resolved_path
+        .id
+        .as_ref()
+        .and_then(|id| doc.paths.get(&id.0))
+        .map(|summary| summary.path.join("::"))
+        .unwrap_or_else(|| resolved_path.name.clone())
+}
+
+/// ----------------------------------------
+/// アイテムID -> 完全パス
+/// paths テーブルにないメソッドは "型 (トレイト) のパス::メソッド名" にする
+/// blanket impl のメソッドは多くの型で共有されるので、候補のうち辞書順で最初のパスにする
+/// (HashMap の順序で結果が変わらないように)
+/// ----------------------------------------
+pub fn item_paths(doc: &RustDocJson) -> HashMap<String, String> {
+    let mut paths: HashMap<String, String> = HashMap::new();
+    let mut add_member = |child: &Id, owner: &str| {
+        let Some(name) = doc.index.get(&child.0).and_then(|c| c.name.as_ref()) else {
+            return;
+        };
+        let path = format!("{}::{}", owner, name);
+        match paths.get(&child.0) {
+            Some(current) if *current <= path => {}
+            _ => {
+                paths.insert(child.0.clone(), path);
+            }
+        }
+    };
+
+    for (id, item) in &doc.index {
+        if let (Some(trait_), Some(summary)) = (&item.inner.trait_, doc.paths.get(id)) {
+            let owner = summary.path.join("::");
+            for child in &trait_.items {
+                add_member(child, &owner);
+            }
+        }
+        let Some(imp) = &item.inner.impl_ else {
+            continue;
+        };
+        let Some(owner) = impl_self_path(doc, imp) else {
+            continue;
+        };
+        for child in &imp.items {
+            add_member(child, &owner);
+        }
+    }
+    // paths テーブルにあるものはそちらを使う
+    for id in doc.index.keys() {
+        if let Some(summary) = doc.paths.get(id) {
+            paths.insert(id.clone(), summary.path.join("::"));
+        }
+    }
+
+    paths
+}
+
+/// ----------------------------------------
+/// impl の対象の型の完全パス
+/// プリミティブ (`impl u32`) なら "u32"
+/// (対象が名前付きの型でもプリミティブでもなければ None)
+/// ----------------------------------------
+pub fn impl_self_path(doc: &RustDocJson, imp: &Impl) -> Option<String> {
+    match &imp.for_ {
+        Type::ResolvedPath { resolved_path } => Some(resolved_path_string(doc, resolved_path)),
+        Type::Primitive { primitive } => Some(primitive.clone()),
+        _ => None,
+    }
+}
+
+/// ----------------------------------------
+/// アイテムID
+/// format_version によって数値だったり文字列だったりするので、
+/// index のキーに合わせて文字列に寄せる
+/// ----------------------------------------
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Id(pub String);
+
+impl<'de> Deserialize<'de> for Id {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawId {
+            Num(u64),
+            Str(String),
+        }
+        let raw = match RawId::deserialize(deserializer)? {
+            RawId::Num(n) => n.to_string(),
+            RawId::Str(s) => s,
+        };
+        Ok(Id(raw))
+    }
+}
+
+/// "root" は1つ (なければ null) なので、ルートの並びにする
+fn deserialize_root<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Id>, D::Error> {
+    Ok(Option::<Id>::deserialize(deserializer)?
+        .into_iter()
+        .collect())
+}
+
+/// ----------------------------------------
+/// JSON のままのドキュメントの ID すべてに接頭辞を付ける
+/// 連結された入力では ID がドキュメントごとに 0 から振られていて衝突するため、
+/// 2つ目以降のドキュメントは読む前にこれで付け替える
+/// ID が入るのは index / paths のキー、"id" と "root"、
+/// "items" / "fields" / "variants" / "impls" / タプルのフィールドの配列、"links" の値
+/// ----------------------------------------
+pub fn prefix_ids(value: &mut Value, prefix: &str) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    for key in ["index", "paths"] {
+        if let Some(Value::Object(entries)) = map.get_mut(key) {
+            *entries = std::mem::take(entries)
+                .into_iter()
+                .map(|(id, entry)| (format!("{}{}", prefix, id), entry))
+                .collect();
+        }
+    }
+    prefix_nested_ids(value, prefix);
+}
+
+fn prefix_nested_ids(value: &mut Value, prefix: &str) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match (key.as_str(), child) {
+                    ("id" | "root", id) => prefix_id(id, prefix),
+                    (
+                        "items" | "fields" | "variants" | "impls" | "implementations",
+                        Value::Array(ids),
+                    ) => {
+                        ids.iter_mut().for_each(|id| prefix_id(id, prefix));
+                    }
+                    // タプル構造体のフィールド (ID か null) と、型のタプル (型のオブジェクト) がある
+                    ("tuple", Value::Array(elements)) => {
+                        for element in elements {
+                            match element {
+                                Value::Number(_) => prefix_id(element, prefix),
+                                other => prefix_nested_ids(other, prefix),
+                            }
+                        }
+                    }
+                    ("links", Value::Object(links)) => {
+                        links.values_mut().for_each(|id| prefix_id(id, prefix));
+                    }
+                    (_, child) => prefix_nested_ids(child, prefix),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for child in values {
+                prefix_nested_ids(child, prefix);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// ID 1つ分 (数値か、古い形式の文字列)。null などはそのまま
+fn prefix_id(id: &mut Value, prefix: &str) {
+    let raw = match id {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => return,
+    };
+    *id = Value::String(format!("{}{}", prefix, raw));
+}
+
+impl RustDocJson {
+    /// ----------------------------------------
+    /// 別のドキュメントのアイテムを取り込む (ID は prefix_ids で付け替え済みのもの)
+    /// crate_id はドキュメントごとの番号なので、名前と URL が同じクレートは同じ番号に、
+    /// そうでなければ空いている番号に付け替える (0 はどのドキュメントでも入力のクレート)
+    /// ----------------------------------------
+    pub fn merge(&mut self, mut other: RustDocJson) {
+        let mut next_id = self
+            .external_crates
+            .keys()
+            .filter_map(|id| id.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let mut crate_ids: HashMap<u32, u32> = HashMap::new();
+        for (old_id, info) in std::mem::take(&mut other.external_crates) {
+            let Ok(old_id) = old_id.parse::<u32>() else {
+                continue;
+            };
+            let existing = self.external_crates.iter().find(|(_, known)| {
+                known.name == info.name && known.html_root_url == info.html_root_url
+            });
+            let new_id = match existing {
+                Some((id, _)) => id.parse().unwrap_or(old_id),
+                None => {
+                    let id = next_id;
+                    next_id += 1;
+                    self.external_crates.insert(id.to_string(), info);
+                    id
+                }
+            };
+            crate_ids.insert(old_id, new_id);
+        }
+        let remap = |crate_id: u32| {
+            if crate_id == 0 {
+                0
+            } else {
+                crate_ids.get(&crate_id).copied().unwrap_or(crate_id)
+            }
+        };
+        for (id, mut item) in other.index {
+            item.crate_id = remap(item.crate_id);
+            self.index.insert(id, item);
+        }
+        for (id, mut summary) in other.paths {
+            summary.crate_id = remap(summary.crate_id);
+            self.paths.insert(id, summary);
+        }
+        self.roots.extend(other.roots);
+    }
 }
 
 /// ----------------------------------------
@@ -21,27 +292,503 @@ pub struct Item {
     /// アイテム名 (function の場合は関数名)
     pub name: Option<String>,
 
+    /// 0 ならこのクレートのアイテム
+    /// (blanket impl 経由で他クレートのアイテムも index に入ってくる)
+    #[serde(default)]
+    pub crate_id: u32,
+
+    /// ソース上の位置 (マクロ生成などでないこともある)
+    #[serde(default)]
+    pub span: Option<Span>,
+
     /// ドキュメントコメント
     #[serde(default)]
     pub docs: Option<String>,
 
+    /// 属性 (#[doc(cfg(...))] など)
+    #[serde(default)]
+    pub attrs: Vec<Attribute>,
+
     /// 詳細情報は "inner" フィールドに入る
     pub inner: ItemInner,
 }
 
+/// ----------------------------------------
+/// ソース上の範囲
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Span {
+    /// クレートのビルド時のカレントディレクトリからの相対パス
+    pub filename: PathBuf,
+    /// (行, 列) どちらも 1 始まり
+    pub begin: (usize, usize),
+    pub end: (usize, usize),
+}
+
+/// ----------------------------------------
+/// アイテムの属性
+/// 古い format では "#[...]" の文字列、新しい format では
+/// { "other": "#[...]" } や { "repr": {...} } のようなオブジェクトになる
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Attribute {
+    /// "#[doc(hidden)]" や "macro_export" のような文字列
+    Text(String),
+    /// { "other": "#[doc(alias = \"x\")]" }
+    Other { other: String },
+    /// repr, must_use など構造化された属性
+    Structured(Value),
+}
+
+/// 属性を "#[...]" 形式の文字列にする
+pub fn attribute_to_string(attr: &Attribute) -> String {
+    match attr {
+        Attribute::Text(text) | Attribute::Other { other: text } => {
+            if text.starts_with("#[") {
+                text.clone()
+            } else {
+                format!("#[{}]", text)
+            }
+        }
+        Attribute::Structured(Value::Object(map)) => match map.keys().next() {
+            Some(key) => format!("#[{}]", key),
+            None => "#[]".to_string(),
+        },
+        Attribute::Structured(other) => format!("#[{}]", other),
+    }
+}
+
 /// ----------------------------------------
 /// ItemInner: functionキーがあれば関数
 /// (他にも struct, enum, trait, impl, ... がありうる)
 /// ----------------------------------------
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct ItemInner {
     /// "function": Option<Function> で関数かどうか判断
     pub function: Option<Function>,
 
-    // もし struct や enum も取り込みたい場合:
-    // pub struct_: Option<StructItem>,
-    // pub enum_: Option<EnumItem>,
-    // etc.
+    /// "impl": impl ブロック
+    #[serde(rename = "impl")]
+    pub impl_: Option<Impl>,
+
+    /// "primitive": std が u32 などに付けるドキュメント
+    pub primitive: Option<Primitive>,
+
+    /// "keyword": 古い format のキーワードのドキュメント
+    /// (新しい format では #[doc(keyword = "...")] 付きの module になる)
+    pub keyword: Option<Value>,
+
+    /// "module": モジュール (クレートのルートも含む)
+    pub module: Option<Module>,
+
+    /// "extern_crate": extern crate 宣言
+    pub extern_crate: Option<ExternCrate>,
+
+    /// "struct": 構造体
+    #[serde(rename = "struct")]
+    pub struct_: Option<Struct>,
+
+    /// "struct_field": 構造体のフィールド (中身はフィールドの型)
+    pub struct_field: Option<Type>,
+
+    /// "union": 共用体 (フィールドは struct_field として別アイテムになる)
+    pub union: Option<Union>,
+
+    /// "enum": 列挙型
+    #[serde(rename = "enum")]
+    pub enum_: Option<Enum>,
+
+    /// "variant": 列挙型のバリアント
+    pub variant: Option<Variant>,
+
+    /// "trait": トレイト定義
+    #[serde(rename = "trait")]
+    pub trait_: Option<Trait>,
+
+    /// "type_alias": 型エイリアス
+    pub type_alias: Option<TypeAlias>,
+
+    /// "constant": 定数
+    pub constant: Option<Constant>,
+
+    /// "assoc_const": トレイトや impl の関連定数
+    pub assoc_const: Option<AssocConst>,
+
+    /// "assoc_type": トレイトや impl の関連型
+    pub assoc_type: Option<AssocType>,
+
+    /// "static": static 変数
+    #[serde(rename = "static")]
+    pub static_: Option<Static>,
+
+    /// "macro": macro_rules! の定義
+    /// rustdoc が整形した "macro_rules! m { ($x:expr) => { ... }; }" の文字列
+    #[serde(rename = "macro")]
+    pub macro_: Option<String>,
+
+    /// "proc_macro": 手続きマクロ (derive / 属性 / 関数風)
+    pub proc_macro: Option<ProcMacro>,
+
+    /// "use": use 宣言 (再エクスポート)
+    #[serde(rename = "use")]
+    pub use_: Option<Use>,
+}
+
+/// ----------------------------------------
+/// プリミティブ型のドキュメントアイテム
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Primitive {
+    /// "u32" など
+    pub name: String,
+}
+
+/// ----------------------------------------
+/// 構造体
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Struct {
+    pub kind: StructKind,
+    #[serde(default)]
+    pub generics: Generics,
+}
+
+/// ----------------------------------------
+/// 構造体の形
+/// "unit" / { "tuple": [...] } / { "plain": { "fields": [...] } }
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StructKind {
+    /// struct Foo;
+    Unit,
+    /// struct Foo(u8, u16); 非公開のフィールドは null
+    Tuple(Vec<Option<Id>>),
+    /// struct Foo { x: u32 }
+    Plain {
+        fields: Vec<Id>,
+        /// 非公開のフィールドが省かれているか
+        #[serde(default)]
+        has_stripped_fields: bool,
+    },
+}
+
+/// ----------------------------------------
+/// 共用体
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Union {
+    pub fields: Vec<Id>,
+    #[serde(default)]
+    pub generics: Generics,
+    /// 非公開のフィールドが省かれているか
+    #[serde(default)]
+    pub has_stripped_fields: bool,
+}
+
+/// ----------------------------------------
+/// 列挙型
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Enum {
+    /// バリアントのID
+    pub variants: Vec<Id>,
+    /// 非公開 (doc(hidden) など) のバリアントが省かれているか
+    #[serde(default)]
+    pub has_stripped_variants: bool,
+    #[serde(default)]
+    pub generics: Generics,
+}
+
+/// ----------------------------------------
+/// 列挙型のバリアント
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Variant {
+    pub kind: VariantKind,
+    /// `X = 1` の判別値
+    #[serde(default)]
+    pub discriminant: Option<Discriminant>,
+}
+
+/// ----------------------------------------
+/// バリアントの形
+/// "plain" / { "tuple": [...] } / { "struct": { "fields": [...] } }
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariantKind {
+    /// A
+    Plain,
+    /// A(u8) 非公開のフィールドは null
+    Tuple(Vec<Option<Id>>),
+    /// A { f: u8 }
+    Struct {
+        fields: Vec<Id>,
+        #[serde(default)]
+        has_stripped_fields: bool,
+    },
+}
+
+/// 判別値 (expr はソースに書かれた式)
+#[derive(Debug, Deserialize)]
+pub struct Discriminant {
+    pub expr: String,
+}
+
+/// ----------------------------------------
+/// トレイト定義
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Trait {
+    #[serde(default)]
+    pub is_unsafe: bool,
+    /// スーパートレイト (`trait Tr: Send + Debug` の Send + Debug)
+    #[serde(default)]
+    pub bounds: Vec<GenericBound>,
+    #[serde(default)]
+    pub generics: Generics,
+    /// メソッドや関連型などのID
+    #[serde(default)]
+    pub items: Vec<Id>,
+}
+
+/// ----------------------------------------
+/// 手続きマクロ
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct ProcMacro {
+    pub kind: ProcMacroKind,
+    /// derive マクロが受け付けるヘルパー属性 (#[serde(...)] の "serde" など)
+    #[serde(default)]
+    pub helpers: Vec<String>,
+}
+
+/// 手続きマクロの種類
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcMacroKind {
+    /// #[proc_macro] (make!(...) の形で呼ぶ)
+    Bang,
+    /// #[proc_macro_attribute]
+    Attr,
+    /// #[proc_macro_derive]
+    Derive,
+}
+
+/// ----------------------------------------
+/// use 宣言
+/// Item.name はなく、名前はここの name に入る
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Use {
+    /// 書かれたパス (例: "inner::Deep", "std::collections::HashMap")
+    pub source: String,
+    /// 公開される名前 (as で付け替えた名前。glob ならモジュール名)
+    pub name: String,
+    /// 指している元のアイテム (プリミティブなどでは None)
+    #[serde(default)]
+    pub id: Option<Id>,
+    /// `use foo::*;` なら true
+    #[serde(default)]
+    pub is_glob: bool,
+}
+
+/// "use inner::Deep;" / "use std::collections::HashMap as Map;" / "use inner::*;"
+pub fn use_to_string(use_: &Use) -> String {
+    let source = render_path(&use_.source);
+    if use_.is_glob {
+        return format!("use {}::*;", source);
+    }
+    let last = use_.source.rsplit("::").next().unwrap_or_default();
+    if last == use_.name {
+        format!("use {};", source)
+    } else {
+        format!("use {} as {};", source, render_ident(&use_.name))
+    }
+}
+
+/// ----------------------------------------
+/// 型エイリアス
+/// 別名の展開などに使えるよう、元の型をそのまま持つ
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct TypeAlias {
+    /// `type Foo = Bar<Baz>;` の Bar<Baz>
+    #[serde(rename = "type")]
+    pub type_: Type,
+    #[serde(default)]
+    pub generics: Generics,
+}
+
+/// ----------------------------------------
+/// 定数
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Constant {
+    #[serde(rename = "type")]
+    pub type_: Type,
+    #[serde(rename = "const")]
+    pub const_: ConstValue,
+}
+
+/// 定数の値 (expr はソースに書かれた式、複雑なものは "_" になる)
+#[derive(Debug, Deserialize)]
+pub struct ConstValue {
+    pub expr: String,
+}
+
+/// ----------------------------------------
+/// 関連定数
+/// トレイトで値を持たない (実装側で決める) ものは value が null
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct AssocConst {
+    #[serde(rename = "type")]
+    pub type_: Type,
+    /// 古い format では "default"
+    #[serde(default, alias = "default")]
+    pub value: Option<String>,
+}
+
+/// ----------------------------------------
+/// 関連型
+/// トレイトでは境界とデフォルト (`type Item: Debug = u32;`)、
+/// impl では実際の型 (`type Item = u32;`) を持つ
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct AssocType {
+    #[serde(default)]
+    pub bounds: Vec<GenericBound>,
+    /// 古い format では "default"
+    #[serde(default, rename = "type", alias = "default")]
+    pub type_: Option<Type>,
+}
+
+/// ----------------------------------------
+/// static 変数
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Static {
+    #[serde(rename = "type")]
+    pub type_: Type,
+    #[serde(default)]
+    pub is_mutable: bool,
+}
+
+/// ----------------------------------------
+/// モジュール
+/// ドキュメント (//! ...) は Item.docs に入る
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Module {
+    /// クレートのルートなら true
+    #[serde(default)]
+    pub is_crate: bool,
+    /// 直下のアイテムのID (impl は含まれない)
+    #[serde(default)]
+    pub items: Vec<Id>,
+}
+
+/// ----------------------------------------
+/// extern crate 宣言
+/// name / rename のどちらが元のクレート名かは format によって揺れるので、
+/// 表示には extern_crate_decl を使う
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct ExternCrate {
+    pub name: String,
+    #[serde(default)]
+    pub rename: Option<String>,
+}
+
+/// "extern crate core as my_core;" の形にする
+pub fn extern_crate_decl(item: &Item, extern_crate: &ExternCrate) -> String {
+    let alias = item.name.as_deref().unwrap_or(&extern_crate.name);
+    let original = [Some(&extern_crate.name), extern_crate.rename.as_ref()]
+        .into_iter()
+        .flatten()
+        .find(|name| name.as_str() != alias);
+    match original {
+        Some(original) => format!("extern crate {} as {};", original, alias),
+        None => format!("extern crate {};", alias),
+    }
+}
+
+/// ----------------------------------------
+/// キーワードのドキュメントアイテムならキーワード名を返す
+/// ----------------------------------------
+pub fn keyword_name(item: &Item) -> Option<String> {
+    if item.inner.keyword.is_some() {
+        return item.name.clone();
+    }
+    // #[doc(keyword = "match")]
+    item.attrs.iter().find_map(|attr| {
+        let text = attribute_to_string(attr);
+        let rest = text.strip_prefix("#[doc(keyword")?;
+        let start = rest.find('"')? + 1;
+        let len = rest[start..].find('"')?;
+        Some(rest[start..start + len].to_string())
+    })
+}
+
+/// ----------------------------------------
+/// #[doc(alias = "...")] で付けられた別名
+/// (#[doc(alias("a", "b"))] の形も読む)
+/// ----------------------------------------
+pub fn doc_aliases(item: &Item) -> Vec<String> {
+    let mut aliases = Vec::new();
+    for attr in &item.attrs {
+        let text = attribute_to_string(attr);
+        let compact: String = text.split_whitespace().collect();
+        if !compact.starts_with("#[doc(alias") {
+            continue;
+        }
+        // 引用符で囲まれた部分を順に拾う
+        aliases.extend(text.split('"').skip(1).step_by(2).map(str::to_string));
+    }
+    aliases
+}
+
+/// ----------------------------------------
+/// #[doc(hidden)] が付いているか
+/// (rustdoc を --document-hidden-items で動かしたときだけ JSON に出てくる)
+/// ----------------------------------------
+pub fn is_doc_hidden(item: &Item) -> bool {
+    item.attrs.iter().any(|attr| {
+        attribute_to_string(attr)
+            .split_whitespace()
+            .collect::<String>()
+            .contains("doc(hidden)")
+    })
+}
+
+/// ----------------------------------------
+/// impl ブロック
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct Impl {
+    /// trait impl なら実装している trait (inherent impl なら None)
+    #[serde(rename = "trait")]
+    pub trait_: Option<ResolvedPath>,
+    /// 実装対象の型
+    #[serde(rename = "for")]
+    pub for_: Type,
+    /// impl 内のアイテム (メソッドなど) のID
+    #[serde(default)]
+    pub items: Vec<Id>,
+    /// `impl !Send for Foo` なら true
+    #[serde(default)]
+    pub is_negative: bool,
+    /// rustdoc が合成した auto trait の impl なら true
+    #[serde(default)]
+    pub is_synthetic: bool,
+    /// blanket impl なら元の impl の対象の型 (`impl<T> Trait for T` の T)
+    #[serde(default)]
+    pub blanket_impl: Option<Type>,
+    #[serde(default)]
+    pub generics: Generics,
 }
 
 /// ----------------------------------------
@@ -51,15 +798,75 @@ pub struct ItemInner {
 pub struct Function {
     /// 関数シグネチャ
     pub sig: FunctionSig,
-    // generics, header, has_body なども
-    // ここに入っているが今回は省略
+    /// unsafe や ABI などの修飾
+    #[serde(default)]
+    pub header: FunctionHeader,
+    /// 本体があるか (trait の必須メソッドや extern ブロック内の宣言は false)
+    /// trait のメソッドなら true がデフォルト実装のある (provided) メソッド
+    #[serde(default = "default_true")]
+    pub has_body: bool,
+    #[serde(default)]
+    pub generics: Generics,
+}
+
+/// ----------------------------------------
+/// 関数ヘッダ: unsafe / ABI など
+/// ----------------------------------------
+#[derive(Debug, Default, Deserialize)]
+pub struct FunctionHeader {
+    /// 古い format では "unsafe_"
+    #[serde(default, alias = "unsafe_")]
+    pub is_unsafe: bool,
+    /// "Rust" や { "C": { "unwind": false } } など
+    #[serde(default)]
+    pub abi: Value,
+}
+
+/// ABI が Rust (指定なし) か
+pub fn is_rust_abi(abi: &Value) -> bool {
+    abi.is_null() || abi == "Rust"
+}
+
+/// ----------------------------------------
+/// ABI をソースに書く形の名前にする (Rust なら None)
+/// "C" / { "C": { "unwind": false } } -> "C"
+/// { "System": { "unwind": true } } -> "system-unwind"
+/// { "Other": "\"efiapi\"" } -> "efiapi"
+/// ----------------------------------------
+pub fn abi_string(abi: &Value) -> Option<String> {
+    if is_rust_abi(abi) {
+        return None;
+    }
+    let (key, detail) = match abi {
+        Value::String(name) => (name.as_str(), &Value::Null),
+        Value::Object(map) => map.iter().next().map(|(k, v)| (k.as_str(), v))?,
+        _ => return None,
+    };
+    let mut name = match key {
+        "C" => "C".to_string(),
+        "Other" => detail
+            .as_str()
+            .unwrap_or_default()
+            .trim_matches('"')
+            .to_string(),
+        // Cdecl, Stdcall, SysV64 など
+        other => other.to_lowercase(),
+    };
+    if detail.get("unwind").and_then(Value::as_bool) == Some(true) {
+        name.push_str("-unwind");
+    }
+    Some(name)
+}
+
+fn default_true() -> bool {
+    true
 }
 
 /// ----------------------------------------
 /// (1) functionかどうかを判定し、
 /// シグネチャ文字列を生成する関数
 /// ----------------------------------------
-pub fn item_to_signature_string(item: &Item) -> Option<String> {
+pub fn item_to_signature_string(item: &Item, options: RenderOptions) -> Option<String> {
     // 関数名
     let name = item.name.as_deref().unwrap_or("unknown");
 
@@ -69,6 +876,667 @@ pub fn item_to_signature_string(item: &Item) -> Option<String> {
     };
 
     // signature_builder側で文字列を作る
-    let sig_str = function_sig_to_string(name, &func.sig);
-    Some(sig_str)
+    let sig_str = function_sig_to_string(name, &func.generics, &func.sig, options);
+
+    // unsafe や extern "C" (extern ブロック内の関数など) を前に付ける
+    Some(header_qualifiers(&func.header) + &sig_str)
+}
+
+/// fn の前に付ける修飾 ("unsafe extern \"C\" " など、なければ空)
+pub fn header_qualifiers(header: &FunctionHeader) -> String {
+    let mut qualifiers = String::new();
+    if header.is_unsafe {
+        qualifiers.push_str("unsafe ");
+    }
+    if let Some(abi) = abi_string(&header.abi) {
+        qualifiers.push_str(&format!("extern \"{}\" ", abi));
+    }
+    qualifiers
+}
+
+/// ----------------------------------------
+/// 宣言を文字列にする
+/// 関数ならシグネチャ、それ以外は "struct Foo { x: u32 }", "enum E { A(u8), C }",
+/// "type Foo = Bar;", "const MAX: usize = 10;" のような宣言
+/// トレイトや impl はメソッドを1行ずつ並べたブロック、macro_rules! はマッチャーの腕を並べたもの、
+/// 手続きマクロは "#[derive(Foo)]" のような使う側の形
+/// (フィールドなどは別アイテムなので doc から引く)
+/// ----------------------------------------
+pub fn item_to_decl_string(
+    doc: &RustDocJson,
+    item: &Item,
+    options: RenderOptions,
+) -> Option<String> {
+    if let Some(sig) = item_to_signature_string(item, options) {
+        return Some(sig);
+    }
+    if let Some(use_) = &item.inner.use_ {
+        return Some(use_to_string(use_));
+    }
+    if let Some(imp) = &item.inner.impl_ {
+        return is_block_impl(imp).then(|| impl_to_string(doc, imp, options));
+    }
+    let name = render_ident(item.name.as_deref()?);
+    if let Some(struct_) = &item.inner.struct_ {
+        return Some(struct_to_string(doc, &name, struct_, options));
+    }
+    if let Some(union) = &item.inner.union {
+        return Some(format!(
+            "union {}{}{} {}",
+            name,
+            generic_params_to_string(&union.generics, options),
+            where_clause_to_string(&union.generics, options),
+            named_fields_string(doc, &union.fields, union.has_stripped_fields, options)
+        ));
+    }
+    if let Some(enum_) = &item.inner.enum_ {
+        return Some(enum_to_string(doc, &name, enum_, options));
+    }
+    if let Some(trait_) = &item.inner.trait_ {
+        return Some(trait_to_string(doc, &name, trait_, options));
+    }
+    if let Some(macro_) = &item.inner.macro_ {
+        // 名前とマッチャーの腕は rustdoc が整形済み (本体は "{ ... }" に省略される)
+        return Some(macro_.trim().to_string());
+    }
+    if let Some(proc_macro) = &item.inner.proc_macro {
+        return Some(proc_macro_to_string(&name, proc_macro));
+    }
+    if let Some(alias) = &item.inner.type_alias {
+        return Some(format!(
+            "type {}{}{} = {};",
+            name,
+            generic_params_to_string(&alias.generics, options),
+            where_clause_to_string(&alias.generics, options),
+            type_to_string(&alias.type_, options)
+        ));
+    }
+    if let Some(constant) = &item.inner.constant {
+        return Some(format!(
+            "const {}: {} = {};",
+            name,
+            type_to_string(&constant.type_, options),
+            constant.const_.expr
+        ));
+    }
+    if let Some(assoc_const) = &item.inner.assoc_const {
+        let ty = type_to_string(&assoc_const.type_, options);
+        return Some(match &assoc_const.value {
+            Some(value) => format!("const {}: {} = {};", name, ty, value),
+            None => format!("const {}: {};", name, ty),
+        });
+    }
+    if let Some(assoc_type) = &item.inner.assoc_type {
+        let mut out = format!("type {}", name);
+        if !assoc_type.bounds.is_empty() {
+            out.push_str(&format!(
+                ": {}",
+                bounds_to_string(&assoc_type.bounds, options)
+            ));
+        }
+        if let Some(ty) = &assoc_type.type_ {
+            out.push_str(&format!(" = {}", type_to_string(ty, options)));
+        }
+        out.push(';');
+        return Some(out);
+    }
+    if let Some(static_) = &item.inner.static_ {
+        // 値は rustdoc と同じく出さない
+        let mutability = if static_.is_mutable { "mut " } else { "" };
+        return Some(format!(
+            "static {}{}: {};",
+            mutability,
+            name,
+            type_to_string(&static_.type_, options)
+        ));
+    }
+    None
+}
+
+/// ----------------------------------------
+/// 宣言の中に入れて表示するアイテム (トレイトのメソッドなど) のID
+/// 一覧ではこれらを単独では出さない
+/// auto trait や blanket impl の中身はブロックとしても出さないが、
+/// 持ち主のない `fn into(self) -> U` などが並ばないようにここに含める
+/// ----------------------------------------
+pub fn nested_item_ids(doc: &RustDocJson) -> HashSet<String> {
+    let mut ids = HashSet::new();
+    for item in doc.index.values() {
+        if let Some(trait_) = &item.inner.trait_ {
+            ids.extend(trait_.items.iter().map(|id| id.0.clone()));
+        }
+        if let Some(imp) = &item.inner.impl_ {
+            ids.extend(imp.items.iter().map(|id| id.0.clone()));
+        }
+    }
+    ids
+}
+
+/// ----------------------------------------
+/// auto trait や blanket impl など、ソースに書かれていない impl の中身のID
+/// (他のクレートのアイテムで、型ごとに同じものが並ぶ)
+/// ----------------------------------------
+pub fn generated_impl_item_ids(doc: &RustDocJson) -> HashSet<String> {
+    doc.index
+        .values()
+        .filter_map(|item| item.inner.impl_.as_ref())
+        .filter(|imp| !is_block_impl(imp))
+        .flat_map(|imp| imp.items.iter().map(|id| id.0.clone()))
+        .collect()
+}
+
+/// ブロックとして表示する impl か
+/// (ソースに書かれた impl だけ。auto trait や blanket impl は型ごとに大量に出るので除く)
+fn is_block_impl(imp: &Impl) -> bool {
+    !imp.is_synthetic && imp.blanket_impl.is_none()
+}
+
+/// 宣言を持つアイテムの種類 ("fn", "struct", "enum", "trait", "impl", "type", "const" など)
+pub fn item_kind(item: &Item) -> Option<&'static str> {
+    if item.inner.function.is_some() {
+        Some("fn")
+    } else if item.inner.struct_.is_some() {
+        Some("struct")
+    } else if item.inner.union.is_some() {
+        Some("union")
+    } else if item.inner.enum_.is_some() {
+        Some("enum")
+    } else if item.inner.trait_.is_some() {
+        Some("trait")
+    } else if item.inner.impl_.is_some() {
+        Some("impl")
+    } else if item.inner.use_.is_some() {
+        Some("use")
+    } else if item.inner.macro_.is_some() {
+        Some("macro")
+    } else if let Some(proc_macro) = &item.inner.proc_macro {
+        Some(match proc_macro.kind {
+            ProcMacroKind::Bang => "macro",
+            ProcMacroKind::Attr => "attr",
+            ProcMacroKind::Derive => "derive",
+        })
+    } else if item.inner.type_alias.is_some() || item.inner.assoc_type.is_some() {
+        Some("type")
+    } else if item.inner.constant.is_some() || item.inner.assoc_const.is_some() {
+        Some("const")
+    } else if item.inner.static_.is_some() {
+        Some("static")
+    } else {
+        None
+    }
+}
+
+fn struct_to_string(
+    doc: &RustDocJson,
+    name: &str,
+    struct_: &Struct,
+    options: RenderOptions,
+) -> String {
+    let name = format!(
+        "{}{}",
+        name,
+        generic_params_to_string(&struct_.generics, options)
+    );
+    // where 節はタプル構造体ならフィールドのあと、そのほかは本体の前
+    let where_clause = where_clause_to_string(&struct_.generics, options);
+    match &struct_.kind {
+        StructKind::Unit => format!("struct {}{};", name, where_clause),
+        StructKind::Tuple(fields) => format!(
+            "struct {}{}{};",
+            name,
+            tuple_fields_string(doc, fields, options),
+            where_clause
+        ),
+        StructKind::Plain {
+            fields,
+            has_stripped_fields,
+        } => format!(
+            "struct {}{} {}",
+            name,
+            where_clause,
+            named_fields_string(doc, fields, *has_stripped_fields, options)
+        ),
+    }
+}
+
+fn enum_to_string(doc: &RustDocJson, name: &str, enum_: &Enum, options: RenderOptions) -> String {
+    let name = format!(
+        "{}{}{}",
+        name,
+        generic_params_to_string(&enum_.generics, options),
+        where_clause_to_string(&enum_.generics, options)
+    );
+    let mut variants: Vec<String> = enum_
+        .variants
+        .iter()
+        .filter_map(|id| {
+            let item = doc.index.get(&id.0)?;
+            let variant = item.inner.variant.as_ref()?;
+            let mut out = render_ident(item.name.as_deref().unwrap_or("_"));
+            match &variant.kind {
+                VariantKind::Plain => {}
+                VariantKind::Tuple(fields) => {
+                    out.push_str(&tuple_fields_string(doc, fields, options))
+                }
+                VariantKind::Struct {
+                    fields,
+                    has_stripped_fields,
+                } => {
+                    out.push(' ');
+                    out.push_str(&named_fields_string(
+                        doc,
+                        fields,
+                        *has_stripped_fields,
+                        options,
+                    ));
+                }
+            }
+            if let Some(discriminant) = &variant.discriminant {
+                out.push_str(&format!(" = {}", discriminant.expr));
+            }
+            Some(out)
+        })
+        .collect();
+    if enum_.has_stripped_variants {
+        variants.push("/* some variants omitted */".to_string());
+    }
+    if variants.is_empty() {
+        format!("enum {} {{}}", name)
+    } else {
+        format!("enum {} {{ {} }}", name, variants.join(", "))
+    }
+}
+
+/// ----------------------------------------
+/// トレイトは中身を並べたブロックにする
+/// デフォルトの本体がある (実装側で書かなくてよい) メソッドには { ... } を付ける
+/// trait Tr {
+///     fn req(self: &Self) -> Self::Item
+///     fn prov(self: &Self) { ... }
+/// }
+/// ----------------------------------------
+fn trait_to_string(
+    doc: &RustDocJson,
+    name: &str,
+    trait_: &Trait,
+    options: RenderOptions,
+) -> String {
+    let unsafety = if trait_.is_unsafe { "unsafe " } else { "" };
+    let mut header = format!(
+        "{}trait {}{}",
+        unsafety,
+        name,
+        generic_params_to_string(&trait_.generics, options)
+    );
+    if !trait_.bounds.is_empty() {
+        header.push_str(&format!(": {}", bounds_to_string(&trait_.bounds, options)));
+    }
+    header.push_str(&where_clause_to_string(&trait_.generics, options));
+    let members = trait_
+        .items
+        .iter()
+        .filter_map(|id| {
+            let item = doc.index.get(&id.0)?;
+            let decl = item_to_decl_string(doc, item, options)?;
+            Some(match &item.inner.function {
+                Some(func) if func.has_body => format!("{} {{ ... }}", decl),
+                _ => decl,
+            })
+        })
+        .collect();
+    block_string(&header, members)
+}
+
+/// ----------------------------------------
+/// impl もトレイトと同じくメソッドを並べたブロックにする
+/// impl<T> Foo<T> {
+///     fn new() -> Self
+/// }
+/// impl std::fmt::Display for Foo {
+///     fn fmt(self: &Self, f: &mut Formatter) -> Result
+/// }
+/// ----------------------------------------
+fn impl_to_string(doc: &RustDocJson, imp: &Impl, options: RenderOptions) -> String {
+    let self_type = type_to_string(&imp.for_, options);
+    let params = generic_params_to_string(&imp.generics, options);
+    let header = match &imp.trait_ {
+        Some(trait_) => {
+            let negation = if imp.is_negative { "!" } else { "" };
+            format!(
+                "impl{} {}{} for {}",
+                params,
+                negation,
+                resolved_path_to_string(trait_, options),
+                self_type
+            )
+        }
+        None => format!("impl{} {}", params, self_type),
+    } + &where_clause_to_string(&imp.generics, options);
+    let members = imp
+        .items
+        .iter()
+        .filter_map(|id| item_to_decl_string(doc, doc.index.get(&id.0)?, options))
+        .collect();
+    block_string(&header, members)
+}
+
+/// "見出し { ... }" の形で中のアイテム (メソッドや関連定数・関連型) を1行ずつ並べる
+fn block_string(header: &str, members: Vec<String>) -> String {
+    if members.is_empty() {
+        return format!("{} {{}}", header);
+    }
+    let mut out = format!("{} {{\n", header);
+    for member in members {
+        out.push_str(&format!("    {}\n", member));
+    }
+    out.push('}');
+    out
+}
+
+/// ----------------------------------------
+/// 手続きマクロを使う側から見た形にする (rustdoc の表示に合わせる)
+/// make!() { /* proc-macro */ } / #[route] / #[derive(Hello)]
+/// derive のヘルパー属性は続く行に並べる
+/// ----------------------------------------
+fn proc_macro_to_string(name: &str, proc_macro: &ProcMacro) -> String {
+    match proc_macro.kind {
+        ProcMacroKind::Bang => format!("{}!() {{ /* proc-macro */ }}", name),
+        ProcMacroKind::Attr => format!("#[{}]", name),
+        ProcMacroKind::Derive => {
+            let mut out = format!("#[derive({})]", name);
+            if !proc_macro.helpers.is_empty() {
+                out.push_str("\n// Attributes available to this derive:");
+                for helper in &proc_macro.helpers {
+                    out.push_str(&format!("\n#[{}]", helper));
+                }
+            }
+            out
+        }
+    }
+}
+
+/// "(u8, u16)" 非公開のフィールドは rustdoc と同じく "_"
+fn tuple_fields_string(doc: &RustDocJson, fields: &[Option<Id>], options: RenderOptions) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| match field {
+            Some(id) => field_type_string(doc, id, options),
+            None => "_".to_string(),
+        })
+        .collect();
+    format!("({})", fields.join(", "))
+}
+
+/// "{ x: u32, /* private fields */ }"
+fn named_fields_string(
+    doc: &RustDocJson,
+    fields: &[Id],
+    has_stripped_fields: bool,
+    options: RenderOptions,
+) -> String {
+    let mut parts: Vec<String> = fields
+        .iter()
+        .map(|id| {
+            let field_name = doc
+                .index
+                .get(&id.0)
+                .and_then(|field| field.name.as_deref())
+                .unwrap_or("_");
+            format!(
+                "{}: {}",
+                render_ident(field_name),
+                field_type_string(doc, id, options)
+            )
+        })
+        .collect();
+    if has_stripped_fields {
+        parts.push("/* private fields */".to_string());
+    }
+    if parts.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", parts.join(", "))
+    }
+}
+
+/// フィールドの型 (引けなければ "_")
+fn field_type_string(doc: &RustDocJson, id: &Id, options: RenderOptions) -> String {
+    doc.index
+        .get(&id.0)
+        .and_then(|field| field.inner.struct_field.as_ref())
+        .map(|ty| type_to_string(ty, options))
+        .unwrap_or_else(|| "_".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::{item_to_decl_string, RustDocJson};
+    use crate::signature_builder::RenderOptions;
+
+    /// index だけの RustDocJson を作って、ID "0" のアイテムの宣言を返す
+    fn decl(index: Value) -> String {
+        decl_with(index, RenderOptions::default())
+    }
+
+    fn decl_with(index: Value, options: RenderOptions) -> String {
+        let doc: RustDocJson = serde_json::from_value(json!({ "index": index })).unwrap();
+        item_to_decl_string(&doc, &doc.index["0"], options).unwrap()
+    }
+
+    fn field(name: &str, ty: Value) -> Value {
+        json!({ "name": name, "inner": { "struct_field": ty } })
+    }
+
+    #[test]
+    fn struct_with_type_and_const_params() {
+        let index = json!({
+            "0": { "name": "Point", "inner": { "struct": {
+                "kind": { "plain": { "fields": [1, 2], "has_stripped_fields": false } },
+                "generics": { "params": [
+                    { "name": "T", "kind": { "type": { "bounds": [], "default": null, "is_synthetic": false } } },
+                    { "name": "N", "kind": { "const": { "type": { "primitive": "usize" }, "default": null } } }
+                ] }
+            } } },
+            "1": field("x", json!({ "generic": "T" })),
+            "2": field("y", json!({ "array": { "type": { "generic": "T" }, "len": "N" } }))
+        });
+        assert_eq!(
+            decl(index),
+            "struct Point<T, const N: usize> { x: T, y: [T; N] }"
+        );
+    }
+
+    #[test]
+    fn type_param_bounds_and_defaults() {
+        let clone = json!({ "trait_bound": { "trait": { "path": "Clone", "id": 9, "args": null }, "modifier": "none" } });
+        let index = json!({
+            "0": { "name": "Wrap", "inner": { "struct": {
+                "kind": { "tuple": [1] },
+                "generics": { "params": [
+                    { "name": "'a", "kind": { "lifetime": { "outlives": [] } } },
+                    { "name": "T", "kind": { "type": {
+                        "bounds": [clone, { "outlives": "'a" }],
+                        "default": { "primitive": "u8" },
+                        "is_synthetic": false
+                    } } }
+                ] }
+            } } },
+            "1": field("0", json!({ "borrowed_ref": { "lifetime": "'a", "is_mutable": false, "type": { "generic": "T" } } }))
+        });
+        assert_eq!(decl(index), "struct Wrap<'a, T: Clone + 'a = u8>(&'a T);");
+    }
+
+    #[test]
+    fn enum_with_lifetime_param() {
+        let error = json!({ "dyn_trait": {
+            "traits": [{ "trait": { "path": "Error", "id": 9, "args": null } }],
+            "lifetime": "'a"
+        } });
+        let index = json!({
+            "0": { "name": "E", "inner": { "enum": {
+                "variants": [1, 2],
+                "generics": { "params": [{ "name": "'a", "kind": { "lifetime": { "outlives": [] } } }] }
+            } } },
+            "1": { "name": "A", "inner": { "variant": { "kind": "plain", "discriminant": null } } },
+            "2": { "name": "B", "inner": { "variant": { "kind": { "tuple": [3] }, "discriminant": null } } },
+            "3": field("0", json!({ "borrowed_ref": { "lifetime": "'a", "is_mutable": false, "type": error } }))
+        });
+        assert_eq!(decl(index), "enum E<'a> { A, B(&'a dyn Error + 'a) }");
+    }
+
+    #[test]
+    fn impl_declares_its_params() {
+        let point = json!({ "resolved_path": { "path": "Point", "id": 5, "args": { "angle_bracketed": {
+            "args": [{ "type": { "generic": "T" } }], "constraints": []
+        } } } });
+        let t = json!({ "name": "T", "kind": { "type": { "bounds": [], "default": null, "is_synthetic": false } } });
+        let index = json!({
+            "0": { "name": null, "inner": { "impl": {
+                "trait": null, "for": point, "items": [1], "generics": { "params": [t] }
+            } } },
+            "1": { "name": "get", "inner": { "function": { "sig": {
+                "inputs": [["self", { "borrowed_ref": { "lifetime": null, "is_mutable": false, "type": { "generic": "Self" } } }]],
+                "output": { "borrowed_ref": { "lifetime": null, "is_mutable": false, "type": { "generic": "T" } } }
+            } } } }
+        });
+        assert_eq!(
+            decl(index),
+            "impl<T> Point<T> {\n    fn get(self: &Self) -> &T\n}"
+        );
+    }
+
+    #[test]
+    fn function_declares_lifetime_and_bounded_type_params() {
+        let fn_bound = json!({ "trait_bound": {
+            "trait": { "path": "Fn", "id": 9, "args": { "parenthesized": {
+                "inputs": [{ "borrowed_ref": { "lifetime": null, "is_mutable": false, "type": { "primitive": "str" } } }],
+                "output": { "primitive": "bool" }
+            } } },
+            "modifier": "none"
+        } });
+        let read = json!({ "trait_bound": { "trait": { "path": "Read", "id": 8, "args": null }, "modifier": "none" } });
+        let index = json!({
+            "0": { "name": "pick", "inner": { "function": {
+                "sig": {
+                    "inputs": [
+                        ["x", { "borrowed_ref": { "lifetime": "'a", "is_mutable": false, "type": { "primitive": "str" } } }],
+                        ["f", { "generic": "F" }],
+                        ["r", { "impl_trait": [read] }]
+                    ],
+                    "output": { "borrowed_ref": { "lifetime": "'a", "is_mutable": false, "type": { "primitive": "str" } } }
+                },
+                "generics": { "params": [
+                    { "name": "'a", "kind": { "lifetime": { "outlives": [] } } },
+                    { "name": "F", "kind": { "type": { "bounds": [fn_bound], "default": null, "is_synthetic": false } } },
+                    { "name": "impl Read", "kind": { "type": { "bounds": [read], "default": null, "is_synthetic": true } } }
+                ] }
+            } } }
+        });
+        assert_eq!(
+            decl(index),
+            "fn pick<'a, F: Fn(&str) -> bool>(x: &'a str, f: F, r: impl Read) -> &'a str"
+        );
+    }
+
+    #[test]
+    fn elided_lifetimes_drop_their_params_and_predicates() {
+        let str_ref = |lifetime: &str| json!({ "borrowed_ref": { "lifetime": lifetime, "is_mutable": false, "type": { "primitive": "str" } } });
+        let index = json!({
+            "0": { "name": "longest", "inner": { "function": {
+                "sig": { "inputs": [["x", str_ref("'a")], ["y", str_ref("'b")]], "output": str_ref("'a") },
+                "generics": {
+                    "params": [
+                        { "name": "'a", "kind": { "lifetime": { "outlives": [] } } },
+                        { "name": "'b", "kind": { "lifetime": { "outlives": [] } } }
+                    ],
+                    "where_predicates": [{ "lifetime_predicate": { "lifetime": "'b", "outlives": ["'a"] } }]
+                }
+            } } }
+        });
+        let options = RenderOptions {
+            elide_lifetimes: true,
+            ..RenderOptions::default()
+        };
+        assert_eq!(
+            decl_with(index, options),
+            "fn longest(x: &str, y: &str) -> &str"
+        );
+    }
+
+    #[test]
+    fn unit_return_is_written_only_when_asked() {
+        let index = json!({
+            "0": { "name": "run", "inner": { "function": { "sig": { "inputs": [], "output": null } } } }
+        });
+        assert_eq!(decl(index.clone()), "fn run()");
+        let options = RenderOptions {
+            show_unit_return: true,
+            ..RenderOptions::default()
+        };
+        assert_eq!(decl_with(index, options), "fn run() -> ()");
+    }
+
+    #[test]
+    fn where_clause_follows_the_signature() {
+        let fn_bound = json!({ "trait_bound": {
+            "trait": { "path": "Fn", "id": 9, "args": { "parenthesized": {
+                "inputs": [{ "borrowed_ref": { "lifetime": null, "is_mutable": false, "type": { "primitive": "str" } } }],
+                "output": { "primitive": "bool" }
+            } } },
+            "modifier": "none"
+        } });
+        let index = json!({
+            "0": { "name": "keep", "inner": { "function": {
+                "sig": { "inputs": [["f", { "generic": "F" }]], "output": null },
+                "generics": {
+                    "params": [
+                        { "name": "'a", "kind": { "lifetime": { "outlives": [] } } },
+                        { "name": "'b", "kind": { "lifetime": { "outlives": [] } } },
+                        { "name": "F", "kind": { "type": { "bounds": [], "default": null, "is_synthetic": false } } }
+                    ],
+                    "where_predicates": [
+                        { "bound_predicate": { "type": { "generic": "F" }, "bounds": [fn_bound], "generic_params": [] } },
+                        { "lifetime_predicate": { "lifetime": "'a", "outlives": ["'b"] } }
+                    ]
+                }
+            } } }
+        });
+        assert_eq!(
+            decl(index),
+            "fn keep<'a, 'b, F>(f: F) where F: Fn(&str) -> bool, 'a: 'b"
+        );
+    }
+
+    #[test]
+    fn where_clause_of_tuple_struct_comes_after_the_fields() {
+        let clone = json!({ "trait_bound": { "trait": { "path": "Clone", "id": 9, "args": null }, "modifier": "none" } });
+        let index = json!({
+            "0": { "name": "Wrap", "inner": { "struct": {
+                "kind": { "tuple": [1] },
+                "generics": {
+                    "params": [{ "name": "T", "kind": { "type": { "bounds": [], "default": null, "is_synthetic": false } } }],
+                    "where_predicates": [{ "bound_predicate": { "type": { "generic": "T" }, "bounds": [clone] } }]
+                }
+            } } },
+            "1": field("0", json!({ "generic": "T" }))
+        });
+        assert_eq!(decl(index), "struct Wrap<T>(T) where T: Clone;");
+    }
+
+    #[test]
+    fn type_alias_keeps_its_params() {
+        let index = json!({
+            "0": { "name": "Alias", "inner": { "type_alias": {
+                "type": { "resolved_path": { "path": "Vec", "id": 5, "args": { "angle_bracketed": {
+                    "args": [{ "type": { "generic": "T" } }], "constraints": []
+                } } } },
+                "generics": { "params": [
+                    { "name": "T", "kind": { "type": { "bounds": [], "default": null, "is_synthetic": false } } }
+                ] }
+            } } }
+        });
+        assert_eq!(decl(index), "type Alias<T> = Vec<T>;");
+    }
 }
@@ -0,0 +1,510 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::crates::crate_label;
+use crate::impl_kind::{method_impl_kinds, ImplKind};
+use crate::modules::{import_paths, reexports};
+use crate::operators::{is_operator_trait, operator_methods};
+use crate::rustdoc_json::{
+    doc_aliases, extern_crate_decl, item_kind, item_paths, item_to_decl_string, keyword_name, Item,
+    RustDocJson,
+};
+use crate::signature_builder::{render_path, RenderOptions, ResolvedPath, Type};
+use crate::usage::usage_example;
+
+/// ----------------------------------------
+/// show で表示する1件分
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct ItemDetail {
+    /// item_kind の種類に加えて "mod", "primitive", "keyword" など
+    pub kind: &'static str,
+    /// 完全パス (キーワードやプリミティブは名前そのもの)
+    pub path: String,
+    /// 0 ならこのクレート
+    pub crate_id: u32,
+    /// 外部クレートのアイテムならそのクレート ("serde 1.0.210")
+    pub crate_label: Option<String>,
+    /// 再エクスポート先のパスで引いたときの元のパス
+    pub reexport_of: Option<String>,
+    /// 宣言 (関数ならシグネチャ)
+    pub signature: Option<String>,
+    /// メソッドならそれを含む impl の種類
+    pub impl_kind: Option<ImplKind>,
+    /// トレイトのメソッドなら、デフォルトの本体がある (provided) か
+    pub provided: Option<bool>,
+    /// #[doc(alias = "...")] の別名
+    pub aliases: Vec<String>,
+    /// 使うのに必要な use 文 (メソッドなら型やトレイトを取り込む)
+    pub import: Option<String>,
+    /// 関数なら呼び出し例 (`let out: u32 = sample::parse(input)?;`)
+    pub example: Option<String>,
+    /// "src/lib.rs:12"
+    pub location: Option<String>,
+    pub docs: Option<String>,
+}
+
+/// アイテムの種類と表示名 (show の対象外なら None)
+fn kind_and_name(item: &Item) -> Option<(&'static str, String)> {
+    if let Some(keyword) = keyword_name(item) {
+        return Some(("keyword", keyword));
+    }
+    if let Some(primitive) = &item.inner.primitive {
+        return Some(("primitive", primitive.name.clone()));
+    }
+    if let Some(kind) = item_kind(item) {
+        return Some((kind, item.name.clone()?));
+    }
+    if let Some(module) = &item.inner.module {
+        let kind = if module.is_crate { "crate" } else { "mod" };
+        return Some((kind, item.name.clone()?));
+    }
+    if item.inner.extern_crate.is_some() {
+        return Some(("extern crate", item.name.clone()?));
+    }
+    None
+}
+
+/// ----------------------------------------
+/// 名前か完全パス (末尾一致も可) で引く
+/// 名前は #[doc(alias = "...")] の別名でもよい (rustdoc の検索と同じ)
+/// 演算子を渡すと、その演算子のトレイト (`+` なら Add) を実装したメソッドを返す
+/// 生識別子 (`r#type`, `sample::r#match::Loop`) でも引ける
+/// 例: "u32", "match", "parse", "sample::parse", "mkdir", "+", "[]"
+/// ----------------------------------------
+pub fn find(doc: &RustDocJson, query: &str, options: RenderOptions) -> Vec<ItemDetail> {
+    // JSON の名前には r# が付いていないので外して比べる
+    let query = query
+        .split("::")
+        .map(|segment| segment.strip_prefix("r#").unwrap_or(segment))
+        .collect::<Vec<_>>()
+        .join("::");
+    let query = query.as_str();
+    let paths = item_paths(doc);
+    let impl_kinds = method_impl_kinds(doc);
+    let trait_members: HashSet<&String> = doc
+        .index
+        .values()
+        .filter_map(|item| item.inner.trait_.as_ref())
+        .flat_map(|trait_| trait_.items.iter().map(|id| &id.0))
+        .collect();
+    let import_paths = import_paths(doc);
+    // トレイトや impl の中身 -> 使うのに use で取り込むべきもののパス
+    // トレイトの impl (blanket impl を含む) のメソッドはトレイトを、inherent impl のメソッドは対象の型
+    // プレリュードのトレイトのように取り込むものがなければ None
+    let mut member_imports: HashMap<&String, Option<String>> = HashMap::new();
+    // impl の中身 -> impl の対象の型 (呼び出し例の Self を書き換えるため)
+    let mut member_self_types: HashMap<&String, &Type> = HashMap::new();
+    for (id, item) in &doc.index {
+        if let Some(trait_) = &item.inner.trait_ {
+            if let Some(import) = import_paths.get(id) {
+                for child in &trait_.items {
+                    member_imports.insert(&child.0, Some(import.clone()));
+                }
+            }
+        }
+        let Some(imp) = &item.inner.impl_ else {
+            continue;
+        };
+        // blanket impl の中身は型ごとに共有されるので対象の型が決まらない
+        if imp.blanket_impl.is_none() {
+            for child in &imp.items {
+                member_self_types.insert(&child.0, &imp.for_);
+            }
+        }
+        let import = match (&imp.trait_, &imp.for_) {
+            (Some(trait_), _) => trait_import_path(doc, &import_paths, trait_),
+            (None, Type::ResolvedPath { resolved_path }) => resolved_path
+                .id
+                .as_ref()
+                .and_then(|self_id| import_paths.get(&self_id.0))
+                .cloned(),
+            (None, _) => None,
+        };
+        for child in &imp.items {
+            member_imports.insert(&child.0, import.clone());
+        }
+    }
+    let suffix = format!("::{}", query);
+
+    // (ID, 表示するパス, 再エクスポートなら元のパス)
+    let mut matches: Vec<(&String, String, Option<String>)> = Vec::new();
+    for (id, item) in &doc.index {
+        let Some((kind, name)) = kind_and_name(item) else {
+            continue;
+        };
+        let path = match kind {
+            "primitive" | "keyword" => name.clone(),
+            _ => paths.get(id).cloned().unwrap_or_else(|| name.clone()),
+        };
+        if name == query
+            || path == query
+            || path.ends_with(&suffix)
+            || doc_aliases(item).iter().any(|alias| alias == query)
+        {
+            matches.push((id, path, None));
+        }
+    }
+    // 演算子 ("+", "[]", "*deref" など) なら、それを実装するトレイトの impl のメソッド
+    let operator_methods = operator_methods(query);
+    if !operator_methods.is_empty() {
+        for item in doc.index.values() {
+            let Some(imp) = &item.inner.impl_ else {
+                continue;
+            };
+            let Some(trait_) = &imp.trait_ else {
+                continue;
+            };
+            // 同じ名前の別のトレイト (自前の `Add` など) と区別するため、paths テーブルの完全パスで比べる
+            let Some(trait_path) = trait_
+                .id
+                .as_ref()
+                .and_then(|id| doc.paths.get(&id.0))
+                .map(|summary| summary.path.join("::"))
+            else {
+                continue;
+            };
+            for child in &imp.items {
+                let Some((id, method)) = doc.index.get_key_value(&child.0) else {
+                    continue;
+                };
+                let is_operator = operator_methods
+                    .iter()
+                    .any(|(wanted_trait, wanted_method)| {
+                        is_operator_trait(&trait_path, wanted_trait)
+                            && method.name.as_deref() == Some(*wanted_method)
+                    });
+                if is_operator {
+                    let path = paths.get(id).cloned().unwrap_or_default();
+                    matches.push((id, path, None));
+                }
+            }
+        }
+    }
+    // 再エクスポート先のパスでも引けるようにする
+    // 名前だけの問い合わせは、同じ名前なら元のアイテムで足りるので
+    // `pub use inner::Deep as Renamed;` のように名前を変えたものだけ
+    let is_path_query = query.contains("::");
+    for reexport in reexports(doc) {
+        if reexport.path != query && !reexport.path.ends_with(&suffix) {
+            continue;
+        }
+        if let Some((id, item)) = doc.index.get_key_value(&reexport.target) {
+            if is_path_query || item.name.as_deref() != Some(query) {
+                let original = paths.get(id).cloned();
+                matches.push((id, reexport.path, original));
+            }
+        }
+    }
+
+    let mut details: Vec<ItemDetail> = matches
+        .into_iter()
+        .filter_map(|(id, path, reexport_of)| {
+            let item = &doc.index[id];
+            let (kind, _) = kind_and_name(item)?;
+            let example = item.inner.function.as_ref().map(|func| {
+                usage_example(&path, func, member_self_types.get(id).copied(), options)
+            });
+            // キーワードやプリミティブ、use 宣言そのものは取り込むものがない
+            let import = match kind {
+                "keyword" | "primitive" | "crate" | "extern crate" | "use" => None,
+                _ => match member_imports.get(id) {
+                    Some(import) => import.as_ref(),
+                    None => import_paths.get(id),
+                }
+                .map(|path| format!("use {};", render_path(path))),
+            };
+            Some(ItemDetail {
+                kind,
+                path,
+                crate_id: item.crate_id,
+                crate_label: crate_label(doc, item.crate_id),
+                reexport_of,
+                signature: match &item.inner.extern_crate {
+                    Some(extern_crate) => Some(extern_crate_decl(item, extern_crate)),
+                    None => item_to_decl_string(doc, item, options),
+                },
+                impl_kind: impl_kinds.get(id).copied(),
+                provided: item
+                    .inner
+                    .function
+                    .as_ref()
+                    .filter(|_| trait_members.contains(id))
+                    .map(|func| func.has_body),
+                aliases: doc_aliases(item),
+                import,
+                example,
+                location: item
+                    .span
+                    .as_ref()
+                    .map(|span| format!("{}:{}", span.filename.display(), span.begin.0)),
+                docs: item.docs.clone(),
+            })
+        })
+        .collect();
+
+    // 同じパスのメソッドは rustdoc HTML と同じく impl の種類の順に並べる
+    details.sort_by(|a, b| (&a.path, a.impl_kind, a.kind).cmp(&(&b.path, b.impl_kind, b.kind)));
+    details
+}
+
+/// ----------------------------------------
+/// トレイトのメソッドを呼ぶために取り込むトレイトのパス
+/// このクレートのものは import_paths のもの
+/// 標準ライブラリ (core / alloc / std) のものは paths テーブルでは
+/// `core::ops::arith::Add` のように非公開のモジュールを含むので、
+/// 公開されている最上位のモジュール直下 `std::ops::Add` にする
+/// (std を使わないクレートなら `core::ops::Add` のまま)
+/// プレリュードにあるトレイト (Clone や Iterator など) は取り込まなくてよいので None
+/// ----------------------------------------
+fn trait_import_path(
+    doc: &RustDocJson,
+    import_paths: &HashMap<String, String>,
+    trait_: &ResolvedPath,
+) -> Option<String> {
+    let id = &trait_.id.as_ref()?.0;
+    let path = import_paths.get(id)?;
+    let is_local = doc
+        .paths
+        .get(id)
+        .is_some_and(|summary| summary.crate_id == 0);
+    let segments: Vec<&str> = path.split("::").collect();
+    match segments.as_slice() {
+        [krate @ ("core" | "alloc" | "std"), module, .., name] if !is_local => {
+            if PRELUDE_TRAITS.contains(&(*module, *name)) {
+                return None;
+            }
+            let uses_std = doc.external_crates.values().any(|info| info.name == "std");
+            let krate = if uses_std { "std" } else { krate };
+            Some(format!("{}::{}::{}", krate, module, name))
+        }
+        _ => Some(path.clone()),
+    }
+}
+
+/// プレリュード (2021 edition) にあるトレイト: (最上位のモジュール, 名前)
+const PRELUDE_TRAITS: &[(&str, &str)] = &[
+    ("marker", "Copy"),
+    ("marker", "Send"),
+    ("marker", "Sized"),
+    ("marker", "Sync"),
+    ("marker", "Unpin"),
+    ("ops", "Drop"),
+    ("ops", "Fn"),
+    ("ops", "FnMut"),
+    ("ops", "FnOnce"),
+    ("convert", "AsMut"),
+    ("convert", "AsRef"),
+    ("convert", "From"),
+    ("convert", "Into"),
+    ("convert", "TryFrom"),
+    ("convert", "TryInto"),
+    ("iter", "DoubleEndedIterator"),
+    ("iter", "ExactSizeIterator"),
+    ("iter", "Extend"),
+    ("iter", "FromIterator"),
+    ("iter", "IntoIterator"),
+    ("iter", "Iterator"),
+    ("clone", "Clone"),
+    ("cmp", "Eq"),
+    ("cmp", "Ord"),
+    ("cmp", "PartialEq"),
+    ("cmp", "PartialOrd"),
+    ("default", "Default"),
+    ("borrow", "ToOwned"),
+    ("string", "ToString"),
+];
+
+/// ----------------------------------------
+/// 1件分を人が読む形にする
+/// ----------------------------------------
+pub fn to_text(detail: &ItemDetail) -> String {
+    // キーワードとプリミティブは名前そのもの (r# を付けない)
+    let path = match detail.kind {
+        "keyword" | "primitive" => detail.path.clone(),
+        _ => render_path(&detail.path),
+    };
+    let mut out = format!("{} {}\n", detail.kind, path);
+    if let Some(signature) = &detail.signature {
+        // トレイトやマクロは複数行になる
+        for line in signature.lines() {
+            out.push_str(&format!("    {}\n", line));
+        }
+    }
+    if !detail.aliases.is_empty() {
+        out.push_str(&format!("    aliases: {}\n", detail.aliases.join(", ")));
+    }
+    if let Some(crate_label) = &detail.crate_label {
+        out.push_str(&format!("    from crate {}\n", crate_label));
+    }
+    if let Some(original) = &detail.reexport_of {
+        out.push_str(&format!("    re-export of {}\n", original));
+    }
+    match detail.provided {
+        Some(true) => out.push_str("    provided method (has a default body)\n"),
+        Some(false) => out.push_str("    required method\n"),
+        None => {}
+    }
+    if let Some(impl_kind) = detail.impl_kind {
+        out.push_str(&format!("    from {} impl\n", impl_kind.label()));
+    }
+    if let Some(import) = &detail.import {
+        out.push_str(&format!("    import: {}\n", import));
+    }
+    if let Some(example) = &detail.example {
+        out.push_str(&format!("    example: {}\n", example));
+    }
+    if let Some(location) = &detail.location {
+        out.push_str(&format!("    at {}\n", location));
+    }
+    if let Some(docs) = detail
+        .docs
+        .as_deref()
+        .filter(|docs| !docs.trim().is_empty())
+    {
+        out.push('\n');
+        for line in docs.trim_end().lines() {
+            let line = format!("    {}", line);
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{find, to_text, ItemDetail, RenderOptions};
+    use crate::rustdoc_json::RustDocJson;
+
+    fn detail(kind: &'static str, path: &str) -> ItemDetail {
+        ItemDetail {
+            kind,
+            path: path.to_string(),
+            crate_id: 0,
+            crate_label: None,
+            reexport_of: None,
+            signature: None,
+            impl_kind: None,
+            provided: None,
+            aliases: Vec::new(),
+            import: None,
+            example: None,
+            location: None,
+            docs: None,
+        }
+    }
+
+    #[test]
+    fn keywords_and_primitives_are_not_escaped() {
+        assert_eq!(to_text(&detail("keyword", "match")), "keyword match\n");
+        assert_eq!(to_text(&detail("primitive", "str")), "primitive str\n");
+    }
+
+    #[test]
+    fn keyword_segments_in_item_paths_are_escaped() {
+        assert_eq!(
+            to_text(&detail("fn", "kw::match::type")),
+            "fn kw::r#match::r#type\n"
+        );
+    }
+
+    /// `pub mod inner { pub struct Deep; }` と `pub use inner::Deep as Renamed;`
+    fn doc_with_renamed_reexport() -> RustDocJson {
+        serde_json::from_value(json!({
+            "root": 0,
+            "index": {
+                "0": { "name": "fx", "inner": { "module": { "is_crate": true, "items": [1, 3] } } },
+                "1": { "name": "inner", "inner": { "module": { "items": [2] } } },
+                "2": { "name": "Deep", "inner": { "struct": { "kind": "unit" } } },
+                "3": {
+                    "name": null,
+                    "inner": { "use": { "source": "inner::Deep", "name": "Renamed", "id": 2, "is_glob": false } }
+                }
+            },
+            "paths": {
+                "2": { "crate_id": 0, "path": ["fx", "inner", "Deep"], "kind": "struct" }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn bare_query_finds_renamed_reexport() {
+        let doc = doc_with_renamed_reexport();
+        let found = find(&doc, "Renamed", RenderOptions::default());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, "fx::Renamed");
+        assert_eq!(found[0].reexport_of.as_deref(), Some("fx::inner::Deep"));
+    }
+
+    #[test]
+    fn bare_query_for_original_name_is_not_duplicated() {
+        let doc = doc_with_renamed_reexport();
+        let paths: Vec<String> = find(&doc, "Deep", RenderOptions::default())
+            .into_iter()
+            .map(|detail| detail.path)
+            .collect();
+        assert_eq!(paths, ["fx::inner::Deep"]);
+    }
+
+    /// `impl core::ops::Add for Foo` と `impl Clone for Foo` (外部クレートは external_crates に渡す)
+    fn doc_with_core_trait_impls(external_crates: serde_json::Value) -> RustDocJson {
+        let foo = json!({ "resolved_path": { "path": "Foo", "id": 1, "args": null } });
+        let method = |name: &str| {
+            json!({
+                "name": name,
+                "inner": { "function": { "sig": { "inputs": [["self", foo]], "output": foo } } }
+            })
+        };
+        serde_json::from_value(json!({
+            "root": 0,
+            "index": {
+                "0": { "name": "fx", "inner": { "module": { "is_crate": true, "items": [1] } } },
+                "1": { "name": "Foo", "inner": { "struct": { "kind": "unit" } } },
+                "2": {
+                    "name": null,
+                    "inner": { "impl": { "trait": { "path": "Add", "id": 20, "args": null }, "for": foo, "items": [4] } }
+                },
+                "3": {
+                    "name": null,
+                    "inner": { "impl": { "trait": { "path": "Clone", "id": 21, "args": null }, "for": foo, "items": [5] } }
+                },
+                "4": method("add"),
+                "5": method("clone")
+            },
+            "paths": {
+                "1": { "crate_id": 0, "path": ["fx", "Foo"], "kind": "struct" },
+                "20": { "crate_id": 1, "path": ["core", "ops", "arith", "Add"], "kind": "trait" },
+                "21": { "crate_id": 1, "path": ["core", "clone", "Clone"], "kind": "trait" }
+            },
+            "external_crates": external_crates
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn std_trait_is_imported_from_its_top_level_module() {
+        let doc =
+            doc_with_core_trait_impls(json!({ "1": { "name": "core" }, "2": { "name": "std" } }));
+        let found = find(&doc, "Foo::add", RenderOptions::default());
+        assert_eq!(found[0].import.as_deref(), Some("use std::ops::Add;"));
+    }
+
+    #[test]
+    fn no_std_crate_keeps_the_core_path() {
+        let doc = doc_with_core_trait_impls(json!({ "1": { "name": "core" } }));
+        let found = find(&doc, "Foo::add", RenderOptions::default());
+        assert_eq!(found[0].import.as_deref(), Some("use core::ops::Add;"));
+    }
+
+    #[test]
+    fn prelude_traits_need_no_import() {
+        let doc =
+            doc_with_core_trait_impls(json!({ "1": { "name": "core" }, "2": { "name": "std" } }));
+        let found = find(&doc, "Foo::clone", RenderOptions::default());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].import, None);
+    }
+}
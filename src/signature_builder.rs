@@ -1,6 +1,19 @@
 use serde::Deserialize;
 use serde_json::Value;
 
+use crate::rustdoc_json::{header_qualifiers, ConstValue, FunctionHeader, Id};
+
+/// ----------------------------------------
+/// 表示の切り替え (コマンドラインで指定されたもの)
+/// ----------------------------------------
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions {
+    /// 戻り値が () のときも `-> ()` と書くか
+    pub show_unit_return: bool,
+    /// ライフタイムを書かない (`Cow<'a, str>` -> `Cow<str>`、`&'a T` -> `&T`)
+    pub elide_lifetimes: bool,
+}
+
 /// ----------------------------------------
 /// 関数シグネチャ (Rustdoc JSON の一部)
 /// ----------------------------------------
@@ -23,36 +36,47 @@ pub struct FunctionSig {
 #[serde(untagged)]
 pub enum Type {
     /// 参照: { "borrowed_ref": { ... } }
-    BorrowedRef {
-        borrowed_ref: BorrowedRefType,
-    },
+    BorrowedRef { borrowed_ref: BorrowedRefType },
 
     /// ユーザー定義型や標準ライブラリの型: { "resolved_path": { ... } }
-    ResolvedPath {
-        resolved_path: ResolvedPath,
-    },
+    ResolvedPath { resolved_path: ResolvedPath },
 
     /// ジェネリック: { "generic": "T" } や { "generic": "Self" } など
-    Generic {
-        generic: String,
-    },
+    Generic { generic: String },
 
     /// プリミティブ型: { "primitive": "str" } や { "primitive": "u32" } など
-    Primitive {
-        primitive: String,
-    },
+    Primitive { primitive: String },
 
     /// タプル型: { "tuple": [ Type, Type, ... ] }
-    Tuple {
-        tuple: Vec<Type>,
-    },
+    Tuple { tuple: Vec<Type> },
 
     /// スライス: { "slice": Type }
-    Slice {
-        slice: Box<Type>,
+    Slice { slice: Box<Type> },
+
+    /// 配列: { "array": { "type": Type, "len": "32" } }
+    Array { array: ArrayType },
+
+    /// 生ポインタ: { "raw_pointer": { ... } }
+    RawPointer { raw_pointer: RawPointerType },
+
+    /// 関連型: { "qualified_path": { ... } } (`<T as Iterator>::Item`, `Self::Output`)
+    QualifiedPath { qualified_path: QualifiedPathType },
+
+    /// トレイトオブジェクト: { "dyn_trait": { ... } } (`dyn Read + Send + 'static`)
+    DynTrait { dyn_trait: DynTraitType },
+
+    /// 引数や戻り値の impl Trait: { "impl_trait": [GenericBound, ...] }
+    ImplTrait { impl_trait: Vec<GenericBound> },
+
+    /// 関数ポインタ: { "function_pointer": { ... } } (`fn(u32) -> bool`)
+    FunctionPointer {
+        function_pointer: Box<FunctionPointerType>,
     },
 
-    /// そのほか (raw_pointer, qualified_pathなど) が出てくる場合は
+    /// 推論に任せる型 `_`: "infer" という文字列で来る
+    Infer(InferMarker),
+
+    /// そのほか (pattern_type など) が出てくる場合は
     /// ここに落ちる
     Other(Value),
 }
@@ -66,12 +90,77 @@ pub struct BorrowedRefType {
     pub inner_type: Box<Type>,
 }
 
+/// Type::Infer / GenericArg::Infer の目印 (文字列 "infer" にだけ合う)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InferMarker {
+    Infer,
+}
+
+/// 配列型: [T; N]
+#[derive(Debug, Deserialize)]
+pub struct ArrayType {
+    #[serde(rename = "type")]
+    pub inner_type: Box<Type>,
+    /// 長さの式 ("32" や "N" など、書かれたとおり)
+    pub len: String,
+}
+
+/// 生ポインタ型: *const T / *mut T
+#[derive(Debug, Deserialize)]
+pub struct RawPointerType {
+    pub is_mutable: bool,
+    #[serde(rename = "type")]
+    pub inner_type: Box<Type>,
+}
+
+/// 関連型へのパス: <self_type as trait_>::name
+#[derive(Debug, Deserialize)]
+pub struct QualifiedPathType {
+    /// 関連型の名前 ("Item")
+    pub name: String,
+    /// 関連型自体のジェネリクス引数 (`Self::Iter<T>` の <T>)
+    #[serde(default)]
+    pub args: Option<GenericArgs>,
+    pub self_type: Box<Type>,
+    /// どのトレイトの関連型か (固有の関連型なら None)
+    #[serde(rename = "trait", default)]
+    pub trait_: Option<ResolvedPath>,
+}
+
+/// トレイトオブジェクト型: dyn Trait1 + Trait2 + 'a
+#[derive(Debug, Deserialize)]
+pub struct DynTraitType {
+    pub traits: Vec<PolyTrait>,
+    pub lifetime: Option<String>,
+}
+
+/// dyn の中のトレイト1つ分 (`for<'a>` の generic_params は今回は省略)
+#[derive(Debug, Deserialize)]
+pub struct PolyTrait {
+    #[serde(rename = "trait")]
+    pub trait_: ResolvedPath,
+}
+
+/// 関数ポインタ型: unsafe extern "C" fn(*const u8) -> i32
+/// (`for<'a>` の generic_params は今回は省略)
+#[derive(Debug, Deserialize)]
+pub struct FunctionPointerType {
+    pub sig: FunctionSig,
+    #[serde(default)]
+    pub header: FunctionHeader,
+}
+
 /// ResolvedPath: 型名 + ジェネリクス引数 (AngleBracketed) など
 #[derive(Debug, Deserialize)]
 pub struct ResolvedPath {
+    /// 新しい format では "path" という名前になっている
+    #[serde(alias = "path")]
     pub name: String,
+    /// paths テーブルを引くためのID
+    #[serde(default)]
+    pub id: Option<Id>,
     pub args: Option<GenericArgs>,
-    // "id" など他にもあり得るが省略
 }
 
 /// ジェネリクスの引数
@@ -79,10 +168,17 @@ pub struct ResolvedPath {
 #[serde(untagged)]
 pub enum GenericArgs {
     /// 例: "angle_bracketed": { "args": [...], "constraints": [...] }
-    AngleBracketed {
-        angle_bracketed: AngleBracketedArgs,
-    },
-    // 他にも "parenthesized" など場合によりあり
+    AngleBracketed { angle_bracketed: AngleBracketedArgs },
+    /// Fn 系トレイトの糖衣構文 `Fn(&str) -> bool`
+    /// 例: "parenthesized": { "inputs": [...], "output": ... }
+    Parenthesized { parenthesized: ParenthesizedArgs },
+}
+
+/// (A, B) -> R
+#[derive(Debug, Deserialize)]
+pub struct ParenthesizedArgs {
+    pub inputs: Vec<Type>,
+    pub output: Option<Box<Type>>,
 }
 
 /// <T, U, ...>
@@ -90,50 +186,238 @@ pub enum GenericArgs {
 pub struct AngleBracketedArgs {
     #[serde(default)]
     pub args: Vec<GenericArg>,
+    /// `Item = u8` や `Item: Clone` (古い format では "bindings")
+    #[serde(default, alias = "bindings")]
+    pub constraints: Vec<AssocItemConstraint>,
+}
+
+/// ----------------------------------------
+/// 関連型への制約 (`Iterator<Item = u8>` の `Item = u8`)
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+pub struct AssocItemConstraint {
+    pub name: String,
+    /// 関連型自体のジェネリクス引数 (`Item<'a> = ...` の <'a>)
     #[serde(default)]
-    pub constraints: Vec<String>,
+    pub args: Option<GenericArgs>,
+    pub binding: AssocItemConstraintKind,
 }
 
-/// ジェネリック引数は型だけとは限らないが、今回は型に限定
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssocItemConstraintKind {
+    /// { "equality": { "type": Type } } (`Item = u8`)、定数なら { "constant": ... }
+    Equality(Term),
+    /// { "constraint": [GenericBound, ...] } (`Item: Clone`)
+    Constraint(Vec<GenericBound>),
+}
+
+/// `=` の右辺: 型か定数
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Term {
+    Type(Type),
+    Constant(Value),
+}
+
+/// ジェネリック引数は型、ライフタイム、定数、推論させる `_`
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 pub enum GenericArg {
-    Type { r#type: Box<Type> },
-    // Lifetime, Const generics などは今回は割愛
+    Type {
+        r#type: Box<Type>,
+    },
+    /// 例: { "lifetime": "'a" } (Cow<'a, str>)
+    Lifetime {
+        lifetime: String,
+    },
+    /// 例: { "const": { "expr": "16", "value": null, "is_literal": true } } (ArrayVec<u8, 16>)
+    Const {
+        #[serde(rename = "const")]
+        const_: ConstValue,
+    },
+    /// "infer" (Foo<_>)
+    Infer(InferMarker),
+}
+
+/// ----------------------------------------
+/// トレイト境界など (`T: Clone + 'a` の右辺の1つ分)
+/// ----------------------------------------
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenericBound {
+    /// { "trait_bound": { "trait": {...}, "modifier": "none" | "maybe" | "maybe_const" } }
+    TraitBound {
+        #[serde(rename = "trait")]
+        trait_: ResolvedPath,
+        #[serde(default)]
+        modifier: String,
+    },
+    /// { "outlives": "'a" }
+    Outlives(String),
+    /// { "use": [...] } (`use<'a, T>` による捕捉の指定)
+    Use(Vec<Value>),
+}
+
+/// ----------------------------------------
+/// 宣言のジェネリクス (`<'a, T: Clone, const N: usize>` と where 節)
+/// ----------------------------------------
+#[derive(Debug, Default, Deserialize)]
+pub struct Generics {
+    #[serde(default)]
+    pub params: Vec<GenericParamDef>,
+    #[serde(default)]
+    pub where_predicates: Vec<WherePredicate>,
+}
+
+/// ジェネリクスの引数1つ分 ("'a" / "T" / "N")
+#[derive(Debug, Deserialize)]
+pub struct GenericParamDef {
+    pub name: String,
+    pub kind: GenericParamDefKind,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenericParamDefKind {
+    /// { "lifetime": { "outlives": ["'b"] } } (`'a: 'b`)
+    Lifetime {
+        #[serde(default)]
+        outlives: Vec<String>,
+    },
+    /// { "type": { "bounds": [...], "default": Type, "is_synthetic": false } }
+    /// 引数位置の `impl Trait` も is_synthetic の型引数として入ってくる
+    Type {
+        #[serde(default)]
+        bounds: Vec<GenericBound>,
+        #[serde(default)]
+        default: Option<Type>,
+        #[serde(default)]
+        is_synthetic: bool,
+    },
+    /// { "const": { "type": Type, "default": "16" } }
+    Const {
+        #[serde(rename = "type")]
+        type_: Type,
+        #[serde(default)]
+        default: Option<String>,
+    },
+}
+
+/// where 節の1つ分
+#[derive(Debug, Deserialize)]
+pub enum WherePredicate {
+    /// { "bound_predicate": { "type": Type, "bounds": [...] } } (`T: Clone`)
+    #[serde(rename = "bound_predicate")]
+    Bound {
+        #[serde(rename = "type")]
+        type_: Type,
+        #[serde(default)]
+        bounds: Vec<GenericBound>,
+    },
+    /// { "lifetime_predicate": { "lifetime": "'a", "outlives": ["'b"] } } (`'a: 'b`)
+    #[serde(rename = "lifetime_predicate")]
+    Lifetime {
+        lifetime: String,
+        #[serde(default)]
+        outlives: Vec<String>,
+    },
+    /// { "eq_predicate": { "lhs": Type, "rhs": { "type": Type } } } (`T::Item = u8`)
+    #[serde(rename = "eq_predicate")]
+    Eq { lhs: Type, rhs: Term },
 }
 
 /// ----------------------------------------
 /// 関数シグネチャをRust風の文字列に
 /// 例: fn load_from_file(path: &str) -> Result<Self, IoError>
+///     fn life<'a>(x: &'a str) -> Cow<'a, str>
 /// ----------------------------------------
-pub fn function_sig_to_string(name: &str, sig: &FunctionSig) -> String {
-    // 引数部分
+pub fn function_sig_to_string(
+    name: &str,
+    generics: &Generics,
+    sig: &FunctionSig,
+    options: RenderOptions,
+) -> String {
+    // "fn name<T>(param1: Ty, param2: Ty)"
+    let mut result = format!(
+        "fn {}{}({})",
+        render_ident(name),
+        generic_params_to_string(generics, options),
+        params_to_string(sig, true, options)
+    );
+    result.push_str(&output_to_string(sig, options));
+    result.push_str(&where_clause_to_string(generics, options));
+    result
+}
+
+/// 引数部分 ("x: u32, y: &str")
+/// 関数ポインタでは名前のない引数 ("_") は型だけにする
+fn params_to_string(sig: &FunctionSig, keep_unnamed: bool, options: RenderOptions) -> String {
     let mut params = Vec::new();
     for (param_name, param_type) in &sig.inputs {
-        let ty_str = type_to_string(param_type);
-        params.push(format!("{}: {}", param_name, ty_str));
+        let ty_str = type_to_string(param_type, options);
+        if !keep_unnamed && (param_name.is_empty() || param_name == "_") {
+            params.push(ty_str);
+        } else {
+            params.push(format!("{}: {}", render_ident(param_name), ty_str));
+        }
+    }
+    // extern "C" fn printf(fmt: *const c_char, ...) のような可変長引数
+    if sig.is_c_variadic {
+        params.push("...".to_string());
     }
+    params.join(", ")
+}
 
-    // "fn name(param1: Ty, param2: Ty)"
-    let mut result = format!("fn {}({})", name, params.join(", "));
+/// 戻り値部分 (" -> Ty")
+/// () はわざわざ表示しない (show_unit_return なら " -> ()")
+fn output_to_string(sig: &FunctionSig, options: RenderOptions) -> String {
+    let out_str = match &sig.output {
+        Some(out_ty) => type_to_string(out_ty, options),
+        None => "()".to_string(),
+    };
+    if out_str == "()" && !options.show_unit_return {
+        String::new()
+    } else {
+        format!(" -> {}", out_str)
+    }
+}
 
-    // 戻り値
-    if let Some(ref out_ty) = sig.output {
-        let out_str = type_to_string(out_ty);
-        if out_str != "()" {
-            // () はわざわざ表示しない
-            result.push_str(" -> ");
-            result.push_str(&out_str);
-        }
+/// 識別子として使うには r# が必要なキーワード
+/// (self / Self / super / crate は r# を付けられないので含めない)
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// ----------------------------------------
+/// rustdoc JSON の名前は r# が外れているので、
+/// キーワードと同じ名前なら r# を付け直す
+/// 例: "type" -> "r#type"
+/// ----------------------------------------
+pub fn render_ident(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
     }
+}
 
-    result
+/// パスの各セグメントに render_ident をかける
+pub fn render_path(path: &str) -> String {
+    path.split("::")
+        .map(render_ident)
+        .collect::<Vec<_>>()
+        .join("::")
 }
 
 /// ----------------------------------------
 /// 型をRustっぽい文字列に変換する
 /// ----------------------------------------
-pub fn type_to_string(ty: &Type) -> String {
+pub fn type_to_string(ty: &Type, options: RenderOptions) -> String {
     match ty {
         Type::BorrowedRef { borrowed_ref } => {
             let mut s = String::new();
@@ -143,63 +427,526 @@ pub fn type_to_string(ty: &Type) -> String {
             }
             // lifetime
             if let Some(ref lt) = borrowed_ref.lifetime {
-                s.push_str(lt);
-                s.push(' ');
+                if !options.elide_lifetimes {
+                    s.push_str(lt);
+                    s.push(' ');
+                }
             }
             // 再帰的に中身を文字列化
-            s.push_str(&type_to_string(&borrowed_ref.inner_type));
-            s
-        }
-        Type::ResolvedPath { resolved_path } => {
-            let mut s = resolved_path.name.clone();
-            // ジェネリクス引数
-            if let Some(ref args) = resolved_path.args {
-                s.push_str(&generic_args_to_string(args));
-            }
+            s.push_str(&type_to_string(&borrowed_ref.inner_type, options));
             s
         }
+        Type::ResolvedPath { resolved_path } => resolved_path_to_string(resolved_path, options),
         Type::Generic { generic } => generic.clone(),
+        // 発散する関数の戻り値 `!` は { "primitive": "never" } で来る
+        Type::Primitive { primitive } if primitive == "never" => "!".to_string(),
         Type::Primitive { primitive } => primitive.clone(),
         Type::Tuple { tuple } => {
             // 例: (T, U, i32)
-            let parts: Vec<String> = tuple.iter().map(|t| type_to_string(t)).collect();
+            let parts: Vec<String> = tuple.iter().map(|ty| type_to_string(ty, options)).collect();
             format!("({})", parts.join(", "))
         }
         Type::Slice { slice } => {
             // 例: [T]
             // 通常Rustでは & [T] がよくあるが、ここでは生スライスとして表示
-            let inner_str = type_to_string(slice);
+            let inner_str = type_to_string(slice, options);
             format!("[{}]", inner_str)
         }
+        Type::Array { array } => {
+            // 例: [u8; 32]
+            format!(
+                "[{}; {}]",
+                type_to_string(&array.inner_type, options),
+                array.len
+            )
+        }
+        Type::RawPointer { raw_pointer } => {
+            // 例: *const c_char / *mut T
+            let mutability = if raw_pointer.is_mutable {
+                "mut"
+            } else {
+                "const"
+            };
+            format!(
+                "*{} {}",
+                mutability,
+                type_to_string(&raw_pointer.inner_type, options)
+            )
+        }
+        Type::QualifiedPath { qualified_path } => {
+            // Self の関連型やトレイトが分からないものは短く Self::Output と書く
+            let self_type = type_to_string(&qualified_path.self_type, options);
+            let mut s = match &qualified_path.trait_ {
+                Some(trait_) if !trait_.name.is_empty() && self_type != "Self" => format!(
+                    "<{} as {}>::{}",
+                    self_type,
+                    resolved_path_to_string(trait_, options),
+                    render_ident(&qualified_path.name)
+                ),
+                _ => format!("{}::{}", self_type, render_ident(&qualified_path.name)),
+            };
+            if let Some(args) = &qualified_path.args {
+                s.push_str(&generic_args_to_string(args, options));
+            }
+            s
+        }
+        Type::DynTrait { dyn_trait } => {
+            // 例: dyn std::error::Error + Send + 'static
+            let mut bounds: Vec<String> = dyn_trait
+                .traits
+                .iter()
+                .map(|poly| resolved_path_to_string(&poly.trait_, options))
+                .collect();
+            if !options.elide_lifetimes {
+                bounds.extend(dyn_trait.lifetime.clone());
+            }
+            format!("dyn {}", bounds.join(" + "))
+        }
+        Type::ImplTrait { impl_trait } => format!("impl {}", bounds_to_string(impl_trait, options)),
+        Type::FunctionPointer { function_pointer } => format!(
+            // 例: unsafe extern "C" fn(*const u8) -> i32
+            "{}fn({}){}",
+            header_qualifiers(&function_pointer.header),
+            params_to_string(&function_pointer.sig, false, options),
+            output_to_string(&function_pointer.sig, options)
+        ),
+        Type::Infer(_) => "_".to_string(),
         Type::Other(val) => {
-            // 予期しない型 (raw_pointer, qualified_pathなど)
+            // 予期しない型 (pattern_type など)
             // いきなりJSON全部を表示すると長いので、簡単にマーカーを入れておく
             format!("/* unknown: {} */", val)
         }
     }
 }
 
+/// ----------------------------------------
+/// パスをジェネリクス引数付きで文字列にする
+/// (型の位置にも trait の位置にも出てくる)
+/// derive で生成された impl のパスは `$crate::fmt::Formatter` のように
+/// マクロの中の書き方のままなので、`$crate::` を外す
+/// ----------------------------------------
+pub fn resolved_path_to_string(resolved_path: &ResolvedPath, options: RenderOptions) -> String {
+    let name = resolved_path
+        .name
+        .strip_prefix("$crate::")
+        .unwrap_or(&resolved_path.name);
+    let mut s = render_path(name);
+    // ジェネリクス引数
+    if let Some(ref args) = resolved_path.args {
+        s.push_str(&generic_args_to_string(args, options));
+    }
+    s
+}
+
+/// ----------------------------------------
+/// 境界を文字列にする (`?Sized`, `~const Clone`, `'a`, `use<'a, T>`)
+/// ----------------------------------------
+pub fn bound_to_string(bound: &GenericBound, options: RenderOptions) -> String {
+    match bound {
+        GenericBound::TraitBound { trait_, modifier } => {
+            let prefix = match modifier.as_str() {
+                "maybe" => "?",
+                "maybe_const" => "~const ",
+                _ => "",
+            };
+            format!("{}{}", prefix, resolved_path_to_string(trait_, options))
+        }
+        GenericBound::Outlives(lifetime) => lifetime.clone(),
+        GenericBound::Use(args) => {
+            // 新しい format では { "lifetime": "'a" } / { "param": "T" }、古いものは文字列
+            let parts: Vec<String> = args
+                .iter()
+                .filter(|arg| !(options.elide_lifetimes && is_lifetime_arg(arg)))
+                .map(|arg| match arg {
+                    Value::String(name) => name.clone(),
+                    Value::Object(map) => map
+                        .values()
+                        .next()
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    other => other.to_string(),
+                })
+                .collect();
+            format!("use<{}>", parts.join(", "))
+        }
+    }
+}
+
+/// ----------------------------------------
+/// 宣言のジェネリクス引数を <...> の文字列に (なければ空)
+/// 例: <'a, T: Clone + 'a, const N: usize>
+/// 引数位置の impl Trait は引数の型のほうに書くので除く
+/// elide_lifetimes ならライフタイムの引数も除く
+/// ----------------------------------------
+pub fn generic_params_to_string(generics: &Generics, options: RenderOptions) -> String {
+    let elide_lifetimes = options.elide_lifetimes;
+    let params: Vec<String> = generics
+        .params
+        .iter()
+        .filter_map(|param| match &param.kind {
+            GenericParamDefKind::Lifetime { .. } if elide_lifetimes => None,
+            GenericParamDefKind::Lifetime { outlives } => {
+                Some(with_bounds(&param.name, &outlives.join(" + ")))
+            }
+            GenericParamDefKind::Type {
+                is_synthetic: true, ..
+            } => None,
+            GenericParamDefKind::Type {
+                bounds, default, ..
+            } => {
+                let mut out = with_bounds(&param.name, &bounds_to_string(bounds, options));
+                if let Some(default) = default {
+                    out.push_str(&format!(" = {}", type_to_string(default, options)));
+                }
+                Some(out)
+            }
+            GenericParamDefKind::Const { type_, default } => {
+                let mut out = format!("const {}: {}", param.name, type_to_string(type_, options));
+                if let Some(default) = default {
+                    out.push_str(&format!(" = {}", default));
+                }
+                Some(out)
+            }
+        })
+        .collect();
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", params.join(", "))
+    }
+}
+
+/// ----------------------------------------
+/// where 節 (" where T: Clone, 'a: 'b"、なければ空)
+/// elide_lifetimes ならライフタイムどうしの制約は除く
+/// ----------------------------------------
+pub fn where_clause_to_string(generics: &Generics, options: RenderOptions) -> String {
+    let elide_lifetimes = options.elide_lifetimes;
+    let predicates: Vec<String> = generics
+        .where_predicates
+        .iter()
+        .filter_map(|predicate| match predicate {
+            WherePredicate::Bound { type_, bounds } => {
+                let bounds = bounds_to_string(bounds, options);
+                (!bounds.is_empty())
+                    .then(|| format!("{}: {}", type_to_string(type_, options), bounds))
+            }
+            WherePredicate::Lifetime { .. } if elide_lifetimes => None,
+            WherePredicate::Lifetime { lifetime, outlives } => {
+                Some(with_bounds(lifetime, &outlives.join(" + ")))
+            }
+            WherePredicate::Eq { lhs, rhs } => Some(format!(
+                "{} = {}",
+                type_to_string(lhs, options),
+                term_to_string(rhs, options)
+            )),
+        })
+        .collect();
+    if predicates.is_empty() {
+        String::new()
+    } else {
+        format!(" where {}", predicates.join(", "))
+    }
+}
+
+/// "T: Clone" (境界がなければ "T")
+fn with_bounds(name: &str, bounds: &str) -> String {
+    if bounds.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}: {}", name, bounds)
+    }
+}
+
+/// "Clone + Send + 'a"
+pub fn bounds_to_string(bounds: &[GenericBound], options: RenderOptions) -> String {
+    bounds
+        .iter()
+        .filter(|bound| !is_elided(bound, options))
+        .map(|bound| bound_to_string(bound, options))
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// elide_lifetimes のときに書かない境界 (`T: 'a`、ライフタイムだけの `use<'a>`)
+fn is_elided(bound: &GenericBound, options: RenderOptions) -> bool {
+    if !options.elide_lifetimes {
+        return false;
+    }
+    match bound {
+        GenericBound::Outlives(_) => true,
+        GenericBound::Use(args) => args.iter().all(is_lifetime_arg),
+        GenericBound::TraitBound { .. } => false,
+    }
+}
+
+/// use<..> の引数がライフタイムか ({ "lifetime": "'a" } または古い形式の "'a")
+fn is_lifetime_arg(arg: &Value) -> bool {
+    match arg {
+        Value::String(name) => name.starts_with('\''),
+        Value::Object(map) => map.contains_key("lifetime"),
+        _ => false,
+    }
+}
+
+/// ----------------------------------------
+/// 型とその中に含まれる型を順にすべて訪問する
+/// ----------------------------------------
+pub fn visit_type(ty: &Type, f: &mut impl FnMut(&Type)) {
+    f(ty);
+    match ty {
+        Type::BorrowedRef { borrowed_ref } => visit_type(&borrowed_ref.inner_type, f),
+        Type::ResolvedPath { resolved_path } => visit_generic_args(&resolved_path.args, f),
+        Type::Tuple { tuple } => {
+            for t in tuple {
+                visit_type(t, f);
+            }
+        }
+        Type::Slice { slice } => visit_type(slice, f),
+        Type::Array { array } => visit_type(&array.inner_type, f),
+        Type::RawPointer { raw_pointer } => visit_type(&raw_pointer.inner_type, f),
+        Type::DynTrait { dyn_trait } => {
+            for poly in &dyn_trait.traits {
+                visit_generic_args(&poly.trait_.args, f);
+            }
+        }
+        Type::ImplTrait { impl_trait } => visit_bounds(impl_trait, f),
+        Type::FunctionPointer { function_pointer } => visit_sig_types(&function_pointer.sig, f),
+        Type::QualifiedPath { qualified_path } => {
+            visit_type(&qualified_path.self_type, f);
+            visit_generic_args(&qualified_path.args, f);
+        }
+        Type::Generic { .. } | Type::Primitive { .. } | Type::Infer(_) | Type::Other(_) => {}
+    }
+}
+
+/// <...> の中の型 (関連型の制約の右辺も含む) を訪問する
+fn visit_generic_args(args: &Option<GenericArgs>, f: &mut impl FnMut(&Type)) {
+    let angle_bracketed = match args {
+        Some(GenericArgs::AngleBracketed { angle_bracketed }) => angle_bracketed,
+        Some(GenericArgs::Parenthesized { parenthesized }) => {
+            for ty in parenthesized
+                .inputs
+                .iter()
+                .chain(parenthesized.output.as_deref())
+            {
+                visit_type(ty, f);
+            }
+            return;
+        }
+        None => return,
+    };
+    for arg in &angle_bracketed.args {
+        if let GenericArg::Type { r#type } = arg {
+            visit_type(r#type, f);
+        }
+    }
+    for constraint in &angle_bracketed.constraints {
+        match &constraint.binding {
+            AssocItemConstraintKind::Equality(Term::Type(ty)) => visit_type(ty, f),
+            AssocItemConstraintKind::Equality(Term::Constant(_)) => {}
+            AssocItemConstraintKind::Constraint(bounds) => visit_bounds(bounds, f),
+        }
+    }
+}
+
+/// 境界に出てくるトレイトの引数の型を訪問する
+fn visit_bounds(bounds: &[GenericBound], f: &mut impl FnMut(&Type)) {
+    for bound in bounds {
+        if let GenericBound::TraitBound { trait_, .. } = bound {
+            visit_generic_args(&trait_.args, f);
+        }
+    }
+}
+
+/// ----------------------------------------
+/// 型に出てくるパスをすべて訪問する
+/// 型の名前だけでなく、`impl Read` や `dyn Write` の境界のトレイトも含む
+/// ----------------------------------------
+pub fn visit_type_paths(ty: &Type, f: &mut impl FnMut(&ResolvedPath)) {
+    visit_type(ty, &mut |ty| type_paths(ty, f));
+}
+
+/// ジェネリクス (引数の境界・デフォルト、where 節) に出てくるパスをすべて訪問する
+pub fn visit_generics_paths(generics: &Generics, f: &mut impl FnMut(&ResolvedPath)) {
+    for param in &generics.params {
+        match &param.kind {
+            GenericParamDefKind::Lifetime { .. } => {}
+            GenericParamDefKind::Type {
+                bounds, default, ..
+            } => {
+                visit_bound_paths(bounds, f);
+                if let Some(default) = default {
+                    visit_type_paths(default, f);
+                }
+            }
+            GenericParamDefKind::Const { type_, .. } => visit_type_paths(type_, f),
+        }
+    }
+    for predicate in &generics.where_predicates {
+        match predicate {
+            WherePredicate::Bound { type_, bounds } => {
+                visit_type_paths(type_, f);
+                visit_bound_paths(bounds, f);
+            }
+            WherePredicate::Lifetime { .. } => {}
+            WherePredicate::Eq { lhs, rhs } => {
+                visit_type_paths(lhs, f);
+                if let Term::Type(rhs) = rhs {
+                    visit_type_paths(rhs, f);
+                }
+            }
+        }
+    }
+}
+
+/// 境界のトレイトと、その引数の型に出てくるパスを訪問する
+fn visit_bound_paths(bounds: &[GenericBound], f: &mut impl FnMut(&ResolvedPath)) {
+    for bound in bounds {
+        if let GenericBound::TraitBound { trait_, .. } = bound {
+            f(trait_);
+        }
+    }
+    visit_bounds(bounds, &mut |ty| type_paths(ty, f));
+}
+
+/// 型そのものが持つパス (中の型は visit_type が別に訪問する)
+fn type_paths(ty: &Type, f: &mut impl FnMut(&ResolvedPath)) {
+    match ty {
+        Type::ResolvedPath { resolved_path } => f(resolved_path),
+        Type::DynTrait { dyn_trait } => {
+            for poly in &dyn_trait.traits {
+                f(&poly.trait_);
+            }
+        }
+        Type::ImplTrait { impl_trait } => {
+            for bound in impl_trait {
+                if let GenericBound::TraitBound { trait_, .. } = bound {
+                    f(trait_);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 関数シグネチャの引数・戻り値に出てくる型をすべて訪問する
+pub fn visit_sig_types(sig: &FunctionSig, f: &mut impl FnMut(&Type)) {
+    for (_, ty) in &sig.inputs {
+        visit_type(ty, f);
+    }
+    if let Some(out) = &sig.output {
+        visit_type(out, f);
+    }
+}
+
 /// ----------------------------------------
 /// ジェネリクス引数を <...> の文字列に
 /// 例: <T, U>
 /// ----------------------------------------
-fn generic_args_to_string(args: &GenericArgs) -> String {
+fn generic_args_to_string(args: &GenericArgs, options: RenderOptions) -> String {
     match args {
         GenericArgs::AngleBracketed { angle_bracketed } => {
-            if angle_bracketed.args.is_empty() {
-                // e.g. "Vec<>" みたいになってしまうなら空を返す
-                "".to_string()
-            } else {
-                let mut parts = Vec::new();
-                for arg in &angle_bracketed.args {
-                    match arg {
-                        GenericArg::Type { r#type } => {
-                            parts.push(type_to_string(r#type));
+            let mut parts = Vec::new();
+            for arg in &angle_bracketed.args {
+                match arg {
+                    GenericArg::Type { r#type } => {
+                        parts.push(type_to_string(r#type, options));
+                    }
+                    GenericArg::Lifetime { lifetime } => {
+                        if !options.elide_lifetimes {
+                            parts.push(lifetime.clone());
                         }
                     }
+                    GenericArg::Const { const_ } => {
+                        parts.push(const_.expr.clone());
+                    }
+                    GenericArg::Infer(_) => parts.push("_".to_string()),
                 }
+            }
+            // 関連型の制約は引数のあとに並ぶ: Iterator<Item = u8>
+            for constraint in &angle_bracketed.constraints {
+                parts.push(constraint_to_string(constraint, options));
+            }
+            if parts.is_empty() {
+                // e.g. "Vec<>" みたいになってしまうなら空を返す
+                "".to_string()
+            } else {
                 format!("<{}>", parts.join(", "))
             }
         }
+        GenericArgs::Parenthesized { parenthesized } => {
+            // 例: Fn(&str) -> bool (() を返すものは矢印を書かない)
+            let inputs: Vec<String> = parenthesized
+                .inputs
+                .iter()
+                .map(|ty| type_to_string(ty, options))
+                .collect();
+            let output = match parenthesized
+                .output
+                .as_deref()
+                .map(|ty| type_to_string(ty, options))
+            {
+                Some(out) if out != "()" || options.show_unit_return => format!(" -> {}", out),
+                _ => String::new(),
+            };
+            format!("({}){}", inputs.join(", "), output)
+        }
+    }
+}
+
+/// "Item = u8" / "Item: Clone"
+fn constraint_to_string(constraint: &AssocItemConstraint, options: RenderOptions) -> String {
+    let mut s = render_ident(&constraint.name);
+    if let Some(args) = &constraint.args {
+        s.push_str(&generic_args_to_string(args, options));
+    }
+    match &constraint.binding {
+        AssocItemConstraintKind::Equality(term) => {
+            format!("{} = {}", s, term_to_string(term, options))
+        }
+        AssocItemConstraintKind::Constraint(bounds) => {
+            format!("{}: {}", s, bounds_to_string(bounds, options))
+        }
+    }
+}
+
+/// `=` の右辺 ("u8" / "16")
+fn term_to_string(term: &Term, options: RenderOptions) -> String {
+    match term {
+        Term::Type(ty) => type_to_string(ty, options),
+        // { "expr": "16", "value": ..., "is_literal": true }
+        Term::Constant(value) => value
+            .get("expr")
+            .and_then(Value::as_str)
+            .unwrap_or("_")
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{type_to_string, RenderOptions, Type};
+
+    fn render(ty: serde_json::Value) -> String {
+        type_to_string(
+            &serde_json::from_value::<Type>(ty).unwrap(),
+            RenderOptions::default(),
+        )
+    }
+
+    #[test]
+    fn macro_crate_prefix_is_stripped() {
+        let formatter = json!({ "resolved_path": {
+            "path": "$crate::fmt::Formatter",
+            "id": 1,
+            "args": { "angle_bracketed": { "args": [{ "lifetime": "'_" }], "constraints": [] } }
+        } });
+        let ty =
+            json!({ "borrowed_ref": { "lifetime": null, "is_mutable": true, "type": formatter } });
+        assert_eq!(render(ty), "&mut fmt::Formatter<'_>");
     }
 }
@@ -0,0 +1,273 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Error as IoError, ErrorKind, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use crate::plugin::spawn_plugin;
+
+/// ----------------------------------------
+/// 出力先
+/// 1回の実行で複数の出力先に (別々の形式で) 書ける
+/// ----------------------------------------
+pub trait Sink {
+    /// content_type は HTTP などで使う (例: "application/json")
+    fn write(&mut self, content: &str, content_type: &str) -> Result<(), IoError>;
+}
+
+/// 標準出力
+pub struct StdoutSink;
+
+impl Sink for StdoutSink {
+    fn write(&mut self, content: &str, _content_type: &str) -> Result<(), IoError> {
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(content.as_bytes())?;
+        stdout.flush()
+    }
+}
+
+/// ファイル (既にあれば上書き)
+pub struct FileSink {
+    pub path: PathBuf,
+}
+
+impl Sink for FileSink {
+    fn write(&mut self, content: &str, _content_type: &str) -> Result<(), IoError> {
+        fs::write(&self.path, content).map_err(|e| {
+            IoError::new(
+                e.kind(),
+                format!("failed to write '{}': {}", self.path.display(), e),
+            )
+        })
+    }
+}
+
+/// HTTP の接続・送受信それぞれの待ち時間の上限
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// ----------------------------------------
+/// http:// の URL に POST する (TLS は非対応)
+/// ----------------------------------------
+pub struct HttpPostSink {
+    /// "host:port" ("[::1]:8080" のような IPv6 も)
+    pub authority: String,
+    /// "/path?query"
+    pub path: String,
+    /// 接続と読み書きのタイムアウト
+    pub timeout: Duration,
+}
+
+impl HttpPostSink {
+    /// "http://host[:port]/path" を分解する (ポートがなければ 80)
+    pub fn parse(url: &str) -> Result<Self, IoError> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| {
+            IoError::other(format!(
+                "unsupported URL '{}': only http:// is supported",
+                url
+            ))
+        })?;
+        let (authority, path) = match rest.find('/') {
+            Some(pos) => (&rest[..pos], &rest[pos..]),
+            None => (rest, "/"),
+        };
+        // IPv6 のアドレス ("[::1]") の中の ':' はポートの区切りではない
+        let has_port = match authority.rfind(']') {
+            Some(end) => authority[end..].contains(':'),
+            None => authority.contains(':'),
+        };
+        let authority = if has_port {
+            authority.to_string()
+        } else {
+            format!("{}:80", authority)
+        };
+        Ok(HttpPostSink {
+            authority,
+            path: path.to_string(),
+            timeout: HTTP_TIMEOUT,
+        })
+    }
+
+    /// 解決できたアドレスに順に、タイムアウト付きで接続する
+    fn connect(&self) -> Result<TcpStream, IoError> {
+        let mut last_error = IoError::new(
+            ErrorKind::NotFound,
+            format!("could not resolve '{}'", self.authority),
+        );
+        for addr in self.authority.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, self.timeout) {
+                Ok(stream) => {
+                    stream.set_read_timeout(Some(self.timeout))?;
+                    stream.set_write_timeout(Some(self.timeout))?;
+                    return Ok(stream);
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+}
+
+impl Sink for HttpPostSink {
+    fn write(&mut self, content: &str, content_type: &str) -> Result<(), IoError> {
+        let mut stream = self.connect().map_err(|e| {
+            IoError::new(
+                e.kind(),
+                format!(
+                    "POST to http://{}{} failed: {}",
+                    self.authority, self.path, e
+                ),
+            )
+        })?;
+        let host = self.authority.trim_end_matches(":80");
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.path,
+            host,
+            content_type,
+            content.len()
+        )?;
+        stream.write_all(content.as_bytes())?;
+        stream.flush()?;
+
+        // ステータス行だけ見る: "HTTP/1.1 200 OK"
+        let mut status_line = String::new();
+        BufReader::new(stream).read_line(&mut status_line)?;
+        let code = status_line.split_whitespace().nth(1).unwrap_or_default();
+        if code.starts_with('2') {
+            Ok(())
+        } else {
+            Err(IoError::other(format!(
+                "POST to http://{}{} failed: {}",
+                self.authority,
+                self.path,
+                status_line.trim()
+            )))
+        }
+    }
+}
+
+/// ----------------------------------------
+/// 外部コマンドの stdin に流す (プラグインとしての出力先)
+/// コマンドの stdout はこのプロセスの stdout にそのまま出る
+/// ----------------------------------------
+pub struct CommandSink {
+    /// 実行するコマンド (引数も書ける。plugin::split_command の規則で分ける)
+    pub command: String,
+}
+
+impl Sink for CommandSink {
+    fn write(&mut self, content: &str, _content_type: &str) -> Result<(), IoError> {
+        let mut child = spawn_plugin(&self.command, Stdio::inherit())?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(content.as_bytes())?;
+        }
+        let status = child.wait()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(IoError::other(format!(
+                "output plugin '{}' exited with {}",
+                self.command, status
+            )))
+        }
+    }
+}
+
+/// ----------------------------------------
+/// 出力先の指定から Sink を作る
+/// "-" は標準出力、http:// で始まれば POST、"|" で始まればコマンド、それ以外はファイル
+/// https:// は書き出す前に (指定の誤りとして) エラーにする
+/// ----------------------------------------
+pub fn open_sink(dest: &str) -> Result<Box<dyn Sink>, IoError> {
+    if dest == "-" {
+        Ok(Box::new(StdoutSink))
+    } else if dest.starts_with("https://") {
+        Err(IoError::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "unsupported URL '{}': https:// is not supported; use http:// or a file",
+                dest
+            ),
+        ))
+    } else if dest.starts_with("http://") {
+        Ok(Box::new(HttpPostSink::parse(dest)?))
+    } else if let Some(command) = dest.strip_prefix('|') {
+        Ok(Box::new(CommandSink {
+            command: command.trim().to_string(),
+        }))
+    } else {
+        Ok(Box::new(FileSink {
+            path: PathBuf::from(dest),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    use super::{open_sink, CommandSink, HttpPostSink, Sink};
+
+    #[test]
+    fn missing_port_defaults_to_80() {
+        let sink = HttpPostSink::parse("http://example.com/hook?x=1").unwrap();
+        assert_eq!(sink.authority, "example.com:80");
+        assert_eq!(sink.path, "/hook?x=1");
+        assert_eq!(
+            HttpPostSink::parse("http://example.com:8080")
+                .unwrap()
+                .authority,
+            "example.com:8080"
+        );
+    }
+
+    #[test]
+    fn bracketed_ipv6_gets_a_port_too() {
+        assert_eq!(
+            HttpPostSink::parse("http://[::1]/").unwrap().authority,
+            "[::1]:80"
+        );
+        assert_eq!(
+            HttpPostSink::parse("http://[::1]:8080/").unwrap().authority,
+            "[::1]:8080"
+        );
+    }
+
+    #[test]
+    fn https_is_rejected_before_writing() {
+        let err = open_sink("https://example.com/hook").err().unwrap();
+        assert!(
+            err.to_string().contains("https:// is not supported"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn silent_server_times_out() {
+        // 接続は受け付けるが応答しないサーバー
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut sink =
+            HttpPostSink::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        sink.timeout = Duration::from_millis(100);
+        assert!(sink.write("{}", "application/json").is_err());
+    }
+
+    #[test]
+    fn command_sink_reports_a_failing_command() {
+        assert!(CommandSink {
+            command: "true".to_string()
+        }
+        .write("x", "text/plain")
+        .is_ok());
+        let err = CommandSink {
+            command: "false".to_string(),
+        }
+        .write("x", "text/plain")
+        .unwrap_err();
+        assert!(err.to_string().contains("output plugin 'false'"), "{}", err);
+    }
+}
@@ -0,0 +1,904 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+
+use quote::ToTokens;
+use serde_json::Value;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+
+use crate::rustdoc_json::{
+    Attribute, ConstValue, Function, FunctionHeader, Id, Impl, Item, ItemInner, ItemSummary,
+    Module, RustDocJson, Span, Trait,
+};
+use crate::signature_builder::{
+    AngleBracketedArgs, ArrayType, AssocItemConstraint, AssocItemConstraintKind, BorrowedRefType,
+    DynTraitType, FunctionPointerType, FunctionSig, GenericArg, GenericArgs, GenericBound,
+    Generics, InferMarker, ParenthesizedArgs, PolyTrait, QualifiedPathType, RawPointerType,
+    ResolvedPath, Term, Type,
+};
+
+/// プリミティブ型として扱う名前
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f16", "f32", "f64", "f128",
+];
+
+/// ----------------------------------------
+/// .rs ファイル (またはディレクトリ) を syn で読み、
+/// Rustdoc JSON と同じ RustDocJson の形に詰めて返す
+/// (nightly や docs ビルドがない環境向けの簡易モード)
+/// ディレクトリなら lib.rs / main.rs (src/ の下も見る) をクレートルートにして
+/// `mod foo;` をたどる。ルートがなければ .rs ファイルをそれぞれ1つのクレートとして読む
+/// ----------------------------------------
+pub fn parse_source(path: &Path) -> Result<RustDocJson, IoError> {
+    let mut collector = Collector::default();
+    match crate_root(path) {
+        Some(root) => collector.collect_crate(&root)?,
+        None => {
+            let mut files = Vec::new();
+            collect_rs_files(path, &mut files)?;
+            files.sort();
+            for file in &files {
+                collector.collect_crate(file)?;
+            }
+        }
+    }
+    Ok(collector.finish())
+}
+
+/// ディレクトリ内のクレートルートのファイル (ファイルならそれ自身)
+fn crate_root(path: &Path) -> Option<PathBuf> {
+    if !path.is_dir() {
+        return Some(path.to_path_buf());
+    }
+    ["src/lib.rs", "lib.rs", "src/main.rs", "main.rs"]
+        .iter()
+        .map(|candidate| path.join(candidate))
+        .find(|candidate| candidate.is_file())
+}
+
+/// ルートのファイルからクレート名を決める
+/// lib.rs / main.rs なら (src/ を飛ばして) ディレクトリ名、それ以外はファイル名
+fn crate_name(root: &Path) -> String {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let stem = root
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = if stem == "lib" || stem == "main" {
+        root.ancestors()
+            .skip(1)
+            .filter_map(|dir| dir.file_name())
+            .find(|dir| *dir != "src")
+            .map(|dir| dir.to_string_lossy().into_owned())
+            .unwrap_or(stem)
+    } else {
+        stem
+    };
+    name.replace('-', "_")
+}
+
+/// ディレクトリなら再帰的に .rs ファイルを集める
+fn collect_rs_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), IoError> {
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            collect_rs_files(&entry?.path(), files)?;
+        }
+    } else if path.extension().is_some_and(|ext| ext == "rs") {
+        files.push(path.to_path_buf());
+    }
+    Ok(())
+}
+
+fn parse_file(file: &Path) -> Result<syn::File, IoError> {
+    let src = fs::read_to_string(file)?;
+    syn::parse_file(&src).map_err(|e| IoError::other(format!("{}: {}", file.display(), e)))
+}
+
+/// ----------------------------------------
+/// アイテムを index と paths に集める
+/// rustdoc と同じく、公開されたモジュールをたどれるアイテムだけを拾い、
+/// メソッドは impl / trait のアイテムの下に置く (パスはその持ち主から決まる)
+/// ----------------------------------------
+#[derive(Default)]
+struct Collector {
+    index: HashMap<String, Item>,
+    paths: HashMap<String, ItemSummary>,
+    roots: Vec<Id>,
+    /// 型の完全パス -> ID (impl の対象の型を解決する)
+    types: HashMap<String, String>,
+    /// impl のIDと、それが書かれたモジュールのパス
+    impls: Vec<(String, Vec<String>)>,
+    /// 次に振る ID
+    next_id: usize,
+}
+
+impl Collector {
+    /// ルートのファイルから1つのクレートを読む
+    fn collect_crate(&mut self, root: &Path) -> Result<(), IoError> {
+        let ast = parse_file(root)?;
+        let name = crate_name(root);
+        let module_path = vec![name.clone()];
+        // クレートルートのファイルでは、子モジュールのファイルは同じディレクトリにある
+        let child_dir = root.parent().unwrap_or(Path::new("")).to_path_buf();
+        let items = self.collect_items(root, &child_dir, &module_path, &ast.items)?;
+        let id = self.push_module(name, doc_string(&ast.attrs), true, items, module_path);
+        self.roots.push(Id(id));
+        Ok(())
+    }
+
+    /// 集め終わったら impl の対象の型を同じモジュールの型に結び付ける
+    fn finish(mut self) -> RustDocJson {
+        for (impl_id, module_path) in &self.impls {
+            let Some(imp) = self
+                .index
+                .get_mut(impl_id)
+                .and_then(|item| item.inner.impl_.as_mut())
+            else {
+                continue;
+            };
+            if let Type::ResolvedPath { resolved_path } = &mut imp.for_ {
+                let full_path = format!("{}::{}", module_path.join("::"), resolved_path.name);
+                if let Some(type_id) = self.types.get(&full_path) {
+                    resolved_path.id = Some(Id(type_id.clone()));
+                }
+            }
+        }
+        RustDocJson {
+            index: self.index,
+            roots: self.roots,
+            paths: self.paths,
+            external_crates: HashMap::new(),
+        }
+    }
+
+    /// rustdoc と同様に連番の ID を振る
+    fn next_id(&mut self) -> String {
+        self.next_id += 1;
+        (self.next_id - 1).to_string()
+    }
+
+    /// ----------------------------------------
+    /// モジュールのアイテム列を走査して index に追加する
+    /// 公開関数・公開トレイト・impl (inherent impl は pub なメソッドだけ)・公開モジュールを拾い、
+    /// モジュールの直下に置くアイテム (impl 以外) のIDを返す
+    /// child_dir は `mod foo;` のファイルを探すディレクトリ
+    /// ----------------------------------------
+    fn collect_items(
+        &mut self,
+        file: &Path,
+        child_dir: &Path,
+        module_path: &[String],
+        items: &[syn::Item],
+    ) -> Result<Vec<Id>, IoError> {
+        let mut members = Vec::new();
+        for item in items {
+            match item {
+                syn::Item::Fn(f) if is_public(&f.vis) => {
+                    let id = self.push_function(file, &f.attrs, &f.sig, &[], FnKind::WithBody);
+                    self.add_path(&id, module_path, &f.sig.ident, "function");
+                    members.push(Id(id));
+                }
+                syn::Item::Struct(s) if is_public(&s.vis) => {
+                    self.add_type(module_path, &s.ident, "struct")
+                }
+                syn::Item::Enum(e) if is_public(&e.vis) => {
+                    self.add_type(module_path, &e.ident, "enum")
+                }
+                syn::Item::Union(u) if is_public(&u.vis) => {
+                    self.add_type(module_path, &u.ident, "union")
+                }
+                syn::Item::Impl(imp) => {
+                    let outer = generic_names(&imp.generics);
+                    let mut methods = Vec::new();
+                    for impl_item in &imp.items {
+                        let syn::ImplItem::Fn(f) = impl_item else {
+                            continue;
+                        };
+                        // inherent impl は pub なメソッドだけ
+                        if imp.trait_.is_none() && !is_public(&f.vis) {
+                            continue;
+                        }
+                        methods.push(Id(self.push_function(
+                            file,
+                            &f.attrs,
+                            &f.sig,
+                            &outer,
+                            FnKind::WithBody,
+                        )));
+                    }
+                    if methods.is_empty() {
+                        continue;
+                    }
+                    let trait_ = imp.trait_.as_ref().and_then(|(_, path, _)| {
+                        match convert_path(path, &outer) {
+                            Type::ResolvedPath { resolved_path } => Some(resolved_path),
+                            _ => None,
+                        }
+                    });
+                    let impl_ = Impl {
+                        trait_,
+                        for_: convert_type(&imp.self_ty, &outer),
+                        items: methods,
+                        is_negative: imp
+                            .trait_
+                            .as_ref()
+                            .is_some_and(|(bang, _, _)| bang.is_some()),
+                        is_synthetic: false,
+                        blanket_impl: None,
+                        generics: Generics::default(),
+                    };
+                    let id = self.next_id();
+                    self.index.insert(
+                        id.clone(),
+                        Item {
+                            name: None,
+                            crate_id: 0,
+                            span: Some(self.span_of(file, imp)),
+                            docs: doc_string(&imp.attrs),
+                            attrs: other_attributes(&imp.attrs),
+                            inner: ItemInner {
+                                impl_: Some(impl_),
+                                ..Default::default()
+                            },
+                        },
+                    );
+                    self.impls.push((id, module_path.to_vec()));
+                }
+                syn::Item::Trait(tr) if is_public(&tr.vis) => {
+                    let outer = generic_names(&tr.generics);
+                    let mut methods = Vec::new();
+                    for trait_item in &tr.items {
+                        if let syn::TraitItem::Fn(f) = trait_item {
+                            let kind = if f.default.is_some() {
+                                FnKind::WithBody
+                            } else {
+                                FnKind::Required
+                            };
+                            methods
+                                .push(Id(self.push_function(file, &f.attrs, &f.sig, &outer, kind)));
+                        }
+                    }
+                    let trait_ = Trait {
+                        is_unsafe: tr.unsafety.is_some(),
+                        bounds: tr
+                            .supertraits
+                            .iter()
+                            .filter_map(|bound| convert_bound(bound, &outer))
+                            .collect(),
+                        generics: Generics::default(),
+                        items: methods,
+                    };
+                    let id = self.next_id();
+                    self.index.insert(
+                        id.clone(),
+                        Item {
+                            name: Some(tr.ident.unraw().to_string()),
+                            crate_id: 0,
+                            span: Some(self.span_of(file, &tr.ident)),
+                            docs: doc_string(&tr.attrs),
+                            attrs: other_attributes(&tr.attrs),
+                            inner: ItemInner {
+                                trait_: Some(trait_),
+                                ..Default::default()
+                            },
+                        },
+                    );
+                    self.add_path(&id, module_path, &tr.ident, "trait");
+                    members.push(Id(id));
+                }
+                syn::Item::ForeignMod(fm) => {
+                    for foreign in &fm.items {
+                        if let syn::ForeignItem::Fn(f) = foreign {
+                            if is_public(&f.vis) {
+                                let kind = FnKind::Foreign(abi_name(&fm.abi));
+                                let id = self.push_function(file, &f.attrs, &f.sig, &[], kind);
+                                self.add_path(&id, module_path, &f.sig.ident, "function");
+                                members.push(Id(id));
+                            }
+                        }
+                    }
+                }
+                // 非公開のモジュールの中身は外から見えないので読まない
+                syn::Item::Mod(m) if is_public(&m.vis) => {
+                    let name = m.ident.unraw().to_string();
+                    let mut path = module_path.to_vec();
+                    path.push(name.clone());
+                    let (docs, items) = match &m.content {
+                        Some((_, content)) => {
+                            let items =
+                                self.collect_items(file, &child_dir.join(&name), &path, content)?;
+                            (doc_string(&m.attrs), items)
+                        }
+                        None => {
+                            // foo.rs (子は foo/ の下) か foo/mod.rs。見つからなければ (cfg で外れたものなど) 飛ばす
+                            let candidates = [
+                                (
+                                    child_dir.join(format!("{}.rs", name)),
+                                    child_dir.join(&name),
+                                ),
+                                (child_dir.join(&name).join("mod.rs"), child_dir.join(&name)),
+                            ];
+                            let Some((module_file, module_dir)) = candidates
+                                .into_iter()
+                                .find(|(module_file, _)| module_file.is_file())
+                            else {
+                                continue;
+                            };
+                            let ast = parse_file(&module_file)?;
+                            let items =
+                                self.collect_items(&module_file, &module_dir, &path, &ast.items)?;
+                            let mut attrs = m.attrs.clone();
+                            attrs.extend(ast.attrs);
+                            (doc_string(&attrs), items)
+                        }
+                    };
+                    members.push(Id(self.push_module(name, docs, false, items, path)));
+                }
+                _ => {}
+            }
+        }
+        Ok(members)
+    }
+
+    /// 関数1つ分を Item にして index へ追加し、ID を返す
+    fn push_function(
+        &mut self,
+        file: &Path,
+        attrs: &[syn::Attribute],
+        sig: &syn::Signature,
+        outer_generics: &[String],
+        kind: FnKind,
+    ) -> String {
+        let item = Item {
+            span: Some(self.span_of(file, sig)),
+            ..function_item(attrs, sig, outer_generics, kind)
+        };
+        let id = self.next_id();
+        self.index.insert(id.clone(), item);
+        id
+    }
+
+    /// モジュール1つ分を Item にして index と paths へ追加し、ID を返す
+    fn push_module(
+        &mut self,
+        name: String,
+        docs: Option<String>,
+        is_crate: bool,
+        items: Vec<Id>,
+        path: Vec<String>,
+    ) -> String {
+        let id = self.next_id();
+        self.index.insert(
+            id.clone(),
+            Item {
+                name: Some(name),
+                crate_id: 0,
+                span: None,
+                docs,
+                attrs: Vec::new(),
+                inner: ItemInner {
+                    module: Some(Module { is_crate, items }),
+                    ..Default::default()
+                },
+            },
+        );
+        self.paths.insert(
+            id.clone(),
+            ItemSummary {
+                crate_id: 0,
+                path,
+                kind: "module".to_string(),
+            },
+        );
+        id
+    }
+
+    /// index に入れたアイテムの完全パスを paths に登録する
+    fn add_path(&mut self, id: &str, module_path: &[String], ident: &syn::Ident, kind: &str) {
+        let mut path = module_path.to_vec();
+        path.push(ident.unraw().to_string());
+        self.paths.insert(
+            id.to_string(),
+            ItemSummary {
+                crate_id: 0,
+                path,
+                kind: kind.to_string(),
+            },
+        );
+    }
+
+    /// 型は宣言を作らず、impl の対象として名前を引けるように paths にだけ登録する
+    fn add_type(&mut self, module_path: &[String], ident: &syn::Ident, kind: &str) {
+        let id = self.next_id();
+        self.add_path(&id, module_path, ident, kind);
+        self.types.insert(self.paths[&id].path.join("::"), id);
+    }
+
+    /// rustdoc と同じく行・列とも 1 始まりの Span にする
+    fn span_of(&self, file: &Path, node: &impl Spanned) -> Span {
+        let span = node.span();
+        let (begin, end) = (span.start(), span.end());
+        Span {
+            filename: file.to_path_buf(),
+            begin: (begin.line, begin.column + 1),
+            end: (end.line, end.column + 1),
+        }
+    }
+}
+
+fn is_public(vis: &syn::Visibility) -> bool {
+    matches!(vis, syn::Visibility::Public(_))
+}
+
+/// ジェネリクスの型パラメータ名一覧
+fn generic_names(generics: &syn::Generics) -> Vec<String> {
+    generics
+        .type_params()
+        .map(|p| p.ident.unraw().to_string())
+        .collect()
+}
+
+/// 関数の置かれ方 (has_body と ABI の決め方が変わる)
+enum FnKind {
+    /// 普通の関数や provided メソッド
+    WithBody,
+    /// trait の必須メソッド
+    Required,
+    /// extern ブロック内の宣言 (ABI 名つき)
+    Foreign(String),
+}
+
+/// extern "C" などの ABI 名 (省略時は "C")
+fn abi_name(abi: &syn::Abi) -> String {
+    abi.name
+        .as_ref()
+        .map(|name| name.value())
+        .unwrap_or_else(|| "C".to_string())
+}
+
+/// 関数1つ分の Item を作る (span は呼び出し側で埋める)
+fn function_item(
+    attrs: &[syn::Attribute],
+    sig: &syn::Signature,
+    outer_generics: &[String],
+    kind: FnKind,
+) -> Item {
+    let mut generics = outer_generics.to_vec();
+    generics.extend(generic_names(&sig.generics));
+
+    let inputs = sig
+        .inputs
+        .iter()
+        .map(|arg| match arg {
+            syn::FnArg::Receiver(recv) => ("self".to_string(), convert_type(&recv.ty, &generics)),
+            syn::FnArg::Typed(pat) => (pat_to_string(&pat.pat), convert_type(&pat.ty, &generics)),
+        })
+        .collect();
+    let output = match &sig.output {
+        syn::ReturnType::Default => None,
+        syn::ReturnType::Type(_, ty) => Some(convert_type(ty, &generics)),
+    };
+
+    let header = match &kind {
+        // extern ブロック内の関数は呼び出しが unsafe
+        FnKind::Foreign(abi) => FunctionHeader {
+            is_unsafe: true,
+            abi: Value::String(abi.clone()),
+        },
+        _ => FunctionHeader {
+            is_unsafe: sig.unsafety.is_some(),
+            abi: Value::String(
+                sig.abi
+                    .as_ref()
+                    .map_or_else(|| "Rust".to_string(), abi_name),
+            ),
+        },
+    };
+
+    Item {
+        name: Some(sig.ident.unraw().to_string()),
+        crate_id: 0,
+        span: None,
+        docs: doc_string(attrs),
+        attrs: other_attributes(attrs),
+        inner: ItemInner {
+            function: Some(Function {
+                sig: FunctionSig {
+                    inputs,
+                    output,
+                    is_c_variadic: sig.variadic.is_some(),
+                },
+                header,
+                has_body: matches!(kind, FnKind::WithBody),
+                generics: Generics::default(),
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+/// `#[doc = "..."]` (/// コメント) を1つの文字列にまとめる
+fn doc_string(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(s),
+                        ..
+                    }),
+                ..
+            }) => {
+                let line = s.value();
+                Some(line.strip_prefix(' ').unwrap_or(&line).to_string())
+            }
+            _ => None,
+        })
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// ドキュメントコメント (`#[doc = "..."]`) 以外の属性を "#[...]" の文字列として残す
+/// (`#[doc(hidden)]` は残す)
+fn other_attributes(attrs: &[syn::Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| {
+            !(attr.path().is_ident("doc") && matches!(attr.meta, syn::Meta::NameValue(_)))
+        })
+        .map(|attr| Attribute::Text(format!("#[{}]", attr.meta.to_token_stream())))
+        .collect()
+}
+
+/// 引数パターンを名前に (`x`, `_`, `(a, b)` など)
+fn pat_to_string(pat: &syn::Pat) -> String {
+    match pat {
+        syn::Pat::Ident(ident) => ident.ident.unraw().to_string(),
+        syn::Pat::Wild(_) => "_".to_string(),
+        other => other.to_token_stream().to_string(),
+    }
+}
+
+/// ----------------------------------------
+/// syn の型を signature_builder::Type に変換する
+/// 対応していない形は Type::Other に落とす
+/// ----------------------------------------
+fn convert_type(ty: &syn::Type, generics: &[String]) -> Type {
+    match ty {
+        syn::Type::Reference(r) => Type::BorrowedRef {
+            borrowed_ref: BorrowedRefType {
+                is_mutable: r.mutability.is_some(),
+                lifetime: r.lifetime.as_ref().map(|lt| lt.to_string()),
+                inner_type: Box::new(convert_type(&r.elem, generics)),
+            },
+        },
+        syn::Type::Path(p) => match &p.qself {
+            None => convert_path(&p.path, generics),
+            Some(qself) => convert_qualified_path(qself, &p.path, generics),
+        },
+        syn::Type::Tuple(t) => Type::Tuple {
+            tuple: t.elems.iter().map(|e| convert_type(e, generics)).collect(),
+        },
+        syn::Type::Slice(s) => Type::Slice {
+            slice: Box::new(convert_type(&s.elem, generics)),
+        },
+        syn::Type::Array(a) => Type::Array {
+            array: ArrayType {
+                inner_type: Box::new(convert_type(&a.elem, generics)),
+                len: a.len.to_token_stream().to_string(),
+            },
+        },
+        syn::Type::Ptr(p) => Type::RawPointer {
+            raw_pointer: RawPointerType {
+                is_mutable: p.mutability.is_some(),
+                inner_type: Box::new(convert_type(&p.elem, generics)),
+            },
+        },
+        syn::Type::TraitObject(t) => {
+            let mut traits = Vec::new();
+            let mut lifetime = None;
+            for bound in &t.bounds {
+                match bound {
+                    syn::TypeParamBound::Trait(tb) => {
+                        if let Type::ResolvedPath { resolved_path } =
+                            convert_path(&tb.path, generics)
+                        {
+                            traits.push(PolyTrait {
+                                trait_: resolved_path,
+                            });
+                        }
+                    }
+                    syn::TypeParamBound::Lifetime(lt) => lifetime = Some(lt.to_string()),
+                    _ => {}
+                }
+            }
+            Type::DynTrait {
+                dyn_trait: DynTraitType { traits, lifetime },
+            }
+        }
+        syn::Type::ImplTrait(t) => Type::ImplTrait {
+            impl_trait: t
+                .bounds
+                .iter()
+                .filter_map(|bound| convert_bound(bound, generics))
+                .collect(),
+        },
+        syn::Type::BareFn(f) => Type::FunctionPointer {
+            function_pointer: Box::new(FunctionPointerType {
+                sig: FunctionSig {
+                    inputs: f
+                        .inputs
+                        .iter()
+                        .map(|arg| {
+                            let name = arg.name.as_ref().map_or_else(
+                                || "_".to_string(),
+                                |(ident, _)| ident.unraw().to_string(),
+                            );
+                            (name, convert_type(&arg.ty, generics))
+                        })
+                        .collect(),
+                    output: match &f.output {
+                        syn::ReturnType::Default => None,
+                        syn::ReturnType::Type(_, ty) => Some(convert_type(ty, generics)),
+                    },
+                    is_c_variadic: f.variadic.is_some(),
+                },
+                header: FunctionHeader {
+                    is_unsafe: f.unsafety.is_some(),
+                    abi: Value::String(f.abi.as_ref().map_or_else(|| "Rust".to_string(), abi_name)),
+                },
+            }),
+        },
+        syn::Type::Infer(_) => Type::Infer(InferMarker::Infer),
+        // rustdoc に合わせて `!` は "never" というプリミティブにする
+        syn::Type::Never(_) => Type::Primitive {
+            primitive: "never".to_string(),
+        },
+        syn::Type::Paren(p) => convert_type(&p.elem, generics),
+        syn::Type::Group(g) => convert_type(&g.elem, generics),
+        other => Type::Other(Value::String(other.to_token_stream().to_string())),
+    }
+}
+
+/// パス型: ジェネリクス / プリミティブ / それ以外 (ResolvedPath) に振り分ける
+fn convert_path(path: &syn::Path, generics: &[String]) -> Type {
+    if let Some(ident) = path.get_ident() {
+        let name = ident.unraw().to_string();
+        if name == "Self" || generics.contains(&name) {
+            return Type::Generic { generic: name };
+        }
+        if PRIMITIVES.contains(&name.as_str()) {
+            return Type::Primitive { primitive: name };
+        }
+    }
+
+    let name = path
+        .segments
+        .iter()
+        .map(|seg| seg.ident.unraw().to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+    // 引数は最後のセグメントのものだけを見る
+    let args = path
+        .segments
+        .last()
+        .and_then(|seg| convert_args(&seg.arguments, generics));
+
+    Type::ResolvedPath {
+        resolved_path: ResolvedPath {
+            name,
+            id: None,
+            args,
+        },
+    }
+}
+
+/// `<T as Iterator>::Item`: qself.position より前がトレイト、残りが関連型
+fn convert_qualified_path(qself: &syn::QSelf, path: &syn::Path, generics: &[String]) -> Type {
+    let trait_segments: Vec<&syn::PathSegment> =
+        path.segments.iter().take(qself.position).collect();
+    let trait_ = trait_segments.last().map(|last| ResolvedPath {
+        name: trait_segments
+            .iter()
+            .map(|seg| seg.ident.unraw().to_string())
+            .collect::<Vec<_>>()
+            .join("::"),
+        id: None,
+        args: convert_args(&last.arguments, generics),
+    });
+    let Some(assoc) = path.segments.iter().nth(qself.position) else {
+        return Type::Other(Value::String(path.to_token_stream().to_string()));
+    };
+    Type::QualifiedPath {
+        qualified_path: QualifiedPathType {
+            name: assoc.ident.unraw().to_string(),
+            args: convert_args(&assoc.arguments, generics),
+            self_type: Box::new(convert_type(&qself.ty, generics)),
+            trait_,
+        },
+    }
+}
+
+/// `<...>` の引数 (型、ライフタイム、定数の引数)
+fn convert_args(arguments: &syn::PathArguments, generics: &[String]) -> Option<GenericArgs> {
+    match arguments {
+        syn::PathArguments::AngleBracketed(angle) => Some(GenericArgs::AngleBracketed {
+            angle_bracketed: AngleBracketedArgs {
+                args: angle
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(GenericArg::Type {
+                            r#type: Box::new(convert_type(ty, generics)),
+                        }),
+                        syn::GenericArgument::Lifetime(lt) => Some(GenericArg::Lifetime {
+                            lifetime: lt.to_string(),
+                        }),
+                        // ArrayVec<u8, 16> / Foo<{ N + 1 }>
+                        syn::GenericArgument::Const(expr) => Some(GenericArg::Const {
+                            const_: ConstValue {
+                                expr: expr.to_token_stream().to_string(),
+                            },
+                        }),
+                        _ => None,
+                    })
+                    .collect(),
+                constraints: angle
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        // Iterator<Item = u8>
+                        syn::GenericArgument::AssocType(assoc) => Some(AssocItemConstraint {
+                            name: assoc.ident.unraw().to_string(),
+                            args: assoc.generics.as_ref().and_then(|g| {
+                                convert_args(
+                                    &syn::PathArguments::AngleBracketed(g.clone()),
+                                    generics,
+                                )
+                            }),
+                            binding: AssocItemConstraintKind::Equality(Term::Type(convert_type(
+                                &assoc.ty, generics,
+                            ))),
+                        }),
+                        // Iterator<Item: Clone>
+                        syn::GenericArgument::Constraint(constraint) => Some(AssocItemConstraint {
+                            name: constraint.ident.unraw().to_string(),
+                            args: None,
+                            binding: AssocItemConstraintKind::Constraint(
+                                constraint
+                                    .bounds
+                                    .iter()
+                                    .filter_map(|bound| convert_bound(bound, generics))
+                                    .collect(),
+                            ),
+                        }),
+                        _ => None,
+                    })
+                    .collect(),
+            },
+        }),
+        // Fn(&str) -> bool
+        syn::PathArguments::Parenthesized(paren) => Some(GenericArgs::Parenthesized {
+            parenthesized: ParenthesizedArgs {
+                inputs: paren
+                    .inputs
+                    .iter()
+                    .map(|ty| convert_type(ty, generics))
+                    .collect(),
+                output: match &paren.output {
+                    syn::ReturnType::Default => None,
+                    syn::ReturnType::Type(_, ty) => Some(Box::new(convert_type(ty, generics))),
+                },
+            },
+        }),
+        syn::PathArguments::None => None,
+    }
+}
+
+/// 境界1つ分 (`Clone`, `?Sized`, `'a`)
+fn convert_bound(bound: &syn::TypeParamBound, generics: &[String]) -> Option<GenericBound> {
+    match bound {
+        syn::TypeParamBound::Trait(tb) => {
+            let Type::ResolvedPath { resolved_path } = convert_path(&tb.path, generics) else {
+                return None;
+            };
+            let modifier = match tb.modifier {
+                syn::TraitBoundModifier::Maybe(_) => "maybe",
+                syn::TraitBoundModifier::None => "none",
+            };
+            Some(GenericBound::TraitBound {
+                trait_: resolved_path,
+                modifier: modifier.to_string(),
+            })
+        }
+        syn::TypeParamBound::Lifetime(lt) => Some(GenericBound::Outlives(lt.to_string())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process;
+
+    use super::parse_source;
+    use crate::rustdoc_json::{item_paths, RustDocJson};
+
+    /// 一時ディレクトリ (名前付き) にファイルを書いてソースとして読む
+    fn parse_crate(name: &str, files: &[(&str, &str)]) -> RustDocJson {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("roogle-source-{}-{}", process::id(), name));
+        for (path, content) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        let doc = parse_source(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        doc
+    }
+
+    fn sorted_paths(doc: &RustDocJson) -> Vec<String> {
+        let mut paths: Vec<String> = item_paths(doc)
+            .into_iter()
+            .filter(|(id, _)| doc.index[id].inner.function.is_some())
+            .map(|(_, path)| path)
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn methods_are_qualified_with_their_owner() {
+        let doc = parse_crate(
+            "owners",
+            &[(
+                "src/lib.rs",
+                "pub struct Foo;
+                 impl Foo { pub fn new() -> Self { Foo } fn private() {} }
+                 pub trait Tr { fn go(&self); }
+                 impl Tr for Foo { fn go(&self) {} }
+                 pub fn free() {}",
+            )],
+        );
+        let name = format!("roogle_source_{}_owners", process::id());
+        let expected: Vec<String> = ["Foo::go", "Foo::new", "Tr::go", "free"]
+            .iter()
+            .map(|path| format!("{}::{}", name, path))
+            .collect();
+        assert_eq!(sorted_paths(&doc), expected);
+    }
+
+    #[test]
+    fn only_public_module_chains_are_read() {
+        let doc = parse_crate(
+            "modules",
+            &[
+                ("src/lib.rs", "pub mod outer; mod private; pub fn top() {}"),
+                (
+                    "src/outer.rs",
+                    "pub mod inner { pub fn deep() {} } mod hidden { pub fn no() {} }",
+                ),
+                ("src/private.rs", "pub fn secret() {}"),
+            ],
+        );
+        let name = format!("roogle_source_{}_modules", process::id());
+        let expected: Vec<String> = ["outer::inner::deep", "top"]
+            .iter()
+            .map(|path| format!("{}::{}", name, path))
+            .collect();
+        assert_eq!(sorted_paths(&doc), expected);
+        assert_eq!(doc.roots.len(), 1);
+    }
+}
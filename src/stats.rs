@@ -0,0 +1,49 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::rustdoc_json::{resolved_path_string, RustDocJson};
+use crate::signature_builder::{visit_sig_types, Type};
+
+/// ----------------------------------------
+/// 型がいくつのアイテムのシグネチャに出てくるか
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct TypeUsage {
+    /// 型の完全パス
+    pub path: String,
+    /// 参照しているアイテム数
+    pub count: usize,
+}
+
+/// ----------------------------------------
+/// このクレートの関数シグネチャから参照されている型を数え、
+/// 多い順 (同数ならパス順) に並べる
+/// 1つのアイテムの中で何度出てきても1回と数える
+/// ----------------------------------------
+pub fn most_referenced_types(doc: &RustDocJson) -> Vec<TypeUsage> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for item in doc.index.values() {
+        if item.crate_id != 0 {
+            continue;
+        }
+        let Some(func) = &item.inner.function else {
+            continue;
+        };
+        let mut seen = BTreeSet::new();
+        visit_sig_types(&func.sig, &mut |ty| {
+            if let Type::ResolvedPath { resolved_path } = ty {
+                seen.insert(resolved_path_string(doc, resolved_path));
+            }
+        });
+        for path in seen {
+            *counts.entry(path).or_default() += 1;
+        }
+    }
+
+    let mut usages: Vec<TypeUsage> = counts
+        .into_iter()
+        .map(|(path, count)| TypeUsage { path, count })
+        .collect();
+    usages.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.path.cmp(&b.path)));
+    usages
+}
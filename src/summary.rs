@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use crate::rustdoc_json::{item_kind, item_to_decl_string, keyword_name, Item, RustDocJson};
+use crate::signature_builder::RenderOptions;
+
+/// ----------------------------------------
+/// 読み込んだ結果の件数
+/// 抽出がクレートを覆えているか (黙って読み飛ばしていないか) を見るためのもの
+/// ----------------------------------------
+#[derive(Debug, Default)]
+pub struct ExtractionSummary {
+    /// 種類 (item_kind の値と "mod") -> 件数
+    pub kinds: BTreeMap<&'static str, usize>,
+    /// 宣言に解釈できなかった型 (/* unknown: ... */) を含むもの
+    pub unknown_types: usize,
+    /// このツールが扱えない種類のアイテム
+    pub skipped: usize,
+}
+
+/// ----------------------------------------
+/// このクレートのアイテムを種類ごとに数える
+/// フィールドやバリアントなど、親の宣言の中に出るものは数えない
+/// (トレイトや impl の中身は数える)
+/// ----------------------------------------
+pub fn summarize(doc: &RustDocJson) -> ExtractionSummary {
+    let mut summary = ExtractionSummary::default();
+    for item in doc.index.values().filter(|item| item.crate_id == 0) {
+        let kind = match item_kind(item) {
+            Some(kind) => kind,
+            None if item.inner.module.is_some() && keyword_name(item).is_none() => "mod",
+            None if is_part_of_parent(item) => continue,
+            None => {
+                summary.skipped += 1;
+                continue;
+            }
+        };
+        // auto trait などの合成された impl は一覧に出さないので数えない
+        let decl = match item_to_decl_string(doc, item, RenderOptions::default()) {
+            Some(decl) => decl,
+            None if kind == "mod" => String::new(),
+            None => continue,
+        };
+        *summary.kinds.entry(kind).or_default() += 1;
+        if decl.contains("/* unknown: ") {
+            summary.unknown_types += 1;
+        }
+    }
+    summary
+}
+
+/// 親の宣言の中や別の表示で扱うので一覧の件数には入れないもの
+fn is_part_of_parent(item: &Item) -> bool {
+    item.inner.struct_field.is_some()
+        || item.inner.variant.is_some()
+        || item.inner.extern_crate.is_some()
+        || item.inner.primitive.is_some()
+        || keyword_name(item).is_some()
+}
+
+/// 表示用の複数形 ("fn" -> "functions")
+fn plural_label(kind: &str) -> &str {
+    match kind {
+        "fn" => "functions",
+        "struct" => "structs",
+        "union" => "unions",
+        "enum" => "enums",
+        "trait" => "traits",
+        "impl" => "impls",
+        "use" => "re-exports",
+        "macro" => "macros",
+        "attr" => "attribute macros",
+        "derive" => "derive macros",
+        "type" => "types",
+        "const" => "constants",
+        "static" => "statics",
+        "mod" => "modules",
+        other => other,
+    }
+}
+
+/// ----------------------------------------
+/// 表にする
+/// ----------------------------------------
+pub fn to_text(summary: &ExtractionSummary) -> String {
+    let total: usize = summary.kinds.values().sum();
+    let mut out = format!("Extracted {} items:\n", total);
+    let mut kinds: Vec<(&&str, &usize)> = summary.kinds.iter().collect();
+    kinds.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    for (kind, count) in kinds {
+        out.push_str(&format!("{:>6}  {}\n", count, plural_label(kind)));
+    }
+    out.push_str(&format!(
+        "{:>6}  with unknown types\n",
+        summary.unknown_types
+    ));
+    out.push_str(&format!(
+        "{:>6}  skipped (unsupported item kind)\n",
+        summary.skipped
+    ));
+    out
+}
@@ -0,0 +1,154 @@
+/// テンプレートで使えるフィールド名
+pub const FIELDS: &[&str] = &[
+    "kind",
+    "name",
+    "path",
+    "sig",
+    "doc_summary",
+    "file",
+    "line",
+    "impl",
+    "module",
+];
+
+/// テンプレートの部品
+#[derive(Debug)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+/// ----------------------------------------
+/// 1件ごとの出力形式を決めるテンプレート
+/// 例: "{kind} {path} {sig} — {doc_summary}"
+/// `{{` / `}}` で波括弧そのものを出す
+/// ----------------------------------------
+#[derive(Debug)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// テンプレート文字列を解釈する (未知のフィールドや閉じ忘れはエラー)
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(ch) => field.push(ch),
+                            None => return Err(format!("unclosed `{{{}` in template", field)),
+                        }
+                    }
+                    let field = field.trim().to_string();
+                    if !FIELDS.contains(&field.as_str()) {
+                        return Err(format!(
+                            "unknown template field `{{{}}}` (available: {})",
+                            field,
+                            FIELDS.join(", ")
+                        ));
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Field(field));
+                }
+                '}' => {
+                    return Err(
+                        "unmatched `}` in template (use `}}` for a literal brace)".to_string()
+                    )
+                }
+                other => literal.push(other),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Template { segments })
+    }
+
+    /// フィールドの値を lookup から引いて埋める
+    pub fn render(&self, lookup: impl Fn(&str) -> String) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Field(field) => out.push_str(&lookup(field)),
+            }
+        }
+        out
+    }
+}
+
+/// ----------------------------------------
+/// ドキュメントの最初の段落を1行にまとめる
+/// (rustdoc の一覧に出る要約と同じ考え方)
+/// ----------------------------------------
+pub fn doc_summary(docs: &str) -> String {
+    docs.lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Template;
+
+    fn render(template: &Template) -> String {
+        template.render(|field| format!("<{}>", field))
+    }
+
+    #[test]
+    fn fields_and_literals() {
+        let template = Template::parse("{kind} {path} — {doc_summary}").unwrap();
+        assert_eq!(render(&template), "<kind> <path> — <doc_summary>");
+    }
+
+    #[test]
+    fn field_names_are_trimmed() {
+        let template = Template::parse("{ name }").unwrap();
+        assert_eq!(render(&template), "<name>");
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        let template = Template::parse("{{{name}}}").unwrap();
+        assert_eq!(render(&template), "{<name>}");
+    }
+
+    #[test]
+    fn unknown_field_is_an_error() {
+        let err = Template::parse("{kind} {nope}").unwrap_err();
+        assert!(err.contains("unknown template field `{nope}`"), "{}", err);
+    }
+
+    #[test]
+    fn unclosed_field_is_an_error() {
+        let err = Template::parse("{kind} {path").unwrap_err();
+        assert!(err.contains("unclosed `{path`"), "{}", err);
+    }
+
+    #[test]
+    fn unmatched_closing_brace_is_an_error() {
+        let err = Template::parse("{kind} }").unwrap_err();
+        assert!(err.contains("unmatched `}`"), "{}", err);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::rustdoc_json::Function;
+use crate::signature_builder::{
+    render_ident, render_path, type_to_string, visit_type, GenericArg, GenericArgs, RenderOptions,
+    Type,
+};
+
+/// ----------------------------------------
+/// 関数の呼び出し例を1行作る
+/// path はその関数の完全パス (メソッドなら "sample::Foo::new")
+/// 例:
+///   let out: u32 = sample::parse(input)?;
+///   let out: Foo = sample::Foo::new();
+///   foo.set(value);
+/// Result / Option を返すものは ? で中身を取り出す
+/// self_type はメソッドなら impl の対象の型 (`Self` を書き換えるのに使う)
+/// ----------------------------------------
+pub fn usage_example(
+    path: &str,
+    func: &Function,
+    self_type: Option<&Type>,
+    options: RenderOptions,
+) -> String {
+    let (owner, name) = path.rsplit_once("::").unwrap_or(("", path));
+
+    let mut receiver = None;
+    let mut args = Vec::new();
+    for (param_name, _) in &func.sig.inputs {
+        if param_name == "self" && receiver.is_none() && args.is_empty() {
+            receiver = Some(variable_name(owner));
+        } else if is_identifier(param_name) {
+            args.push(render_ident(param_name));
+        } else {
+            // `_` や `(a, b)` のようなパターンは変数名にできない
+            args.push("todo!()".to_string());
+        }
+    }
+    let mut call = match receiver {
+        // メソッド呼び出し: foo.name(args)
+        Some(receiver) => format!("{}.{}({})", receiver, render_ident(name), args.join(", ")),
+        None => format!("{}({})", render_path(path), args.join(", ")),
+    };
+
+    // 戻り値: Result / Option なら ? を付けて中身の型を見る
+    let mut output = func.sig.output.as_ref();
+    if let Some(inner) = output.and_then(fallible_inner) {
+        call.push('?');
+        output = Some(inner);
+    }
+    let statement = match output {
+        None => format!("{};", call),
+        Some(Type::Tuple { tuple }) if tuple.is_empty() => format!("{};", call),
+        // 戻ってこない関数 (-> !)
+        Some(Type::Primitive { primitive }) if primitive == "never" => format!("{};", call),
+        // Self は impl の対象の型に、ジェネリクスなどが入る型は書かずに推論に任せる
+        Some(Type::Generic { generic }) if generic == "Self" => match self_type {
+            Some(self_type) if is_concrete(self_type) => {
+                format!(
+                    "let out: {} = {};",
+                    type_to_string(self_type, options),
+                    call
+                )
+            }
+            _ => format!("let out = {};", call),
+        },
+        Some(ty) if is_concrete(ty) => {
+            format!("let out: {} = {};", type_to_string(ty, options), call)
+        }
+        Some(_) => format!("let out = {};", call),
+    };
+
+    if func.header.is_unsafe {
+        format!("unsafe {{ {} }}", statement)
+    } else {
+        statement
+    }
+}
+
+/// Result<T, E> / Option<T> (io::Result<T> なども) なら T
+fn fallible_inner(ty: &Type) -> Option<&Type> {
+    let Type::ResolvedPath { resolved_path } = ty else {
+        return None;
+    };
+    let last = resolved_path.name.rsplit("::").next().unwrap_or_default();
+    if last != "Result" && last != "Option" {
+        return None;
+    }
+    let Some(GenericArgs::AngleBracketed { angle_bracketed }) = &resolved_path.args else {
+        return None;
+    };
+    // Cow<'a, T> のようなライフタイムは飛ばして最初の型
+    angle_bracketed.args.iter().find_map(|arg| match arg {
+        GenericArg::Type { r#type } => Some(r#type.as_ref()),
+        GenericArg::Lifetime { .. } | GenericArg::Const { .. } | GenericArg::Infer(_) => None,
+    })
+}
+
+/// ジェネリクスや impl Trait、名前付きのライフタイム、解釈できなかった型を含まないか
+/// (そのまま型注釈に書けるか)
+fn is_concrete(ty: &Type) -> bool {
+    let mut concrete = true;
+    visit_type(ty, &mut |t| {
+        if has_named_lifetime(t)
+            || matches!(
+                t,
+                Type::Generic { .. } | Type::ImplTrait { .. } | Type::Infer(_) | Type::Other(_)
+            )
+        {
+            concrete = false;
+        }
+    });
+    concrete
+}
+
+/// 'static と '_ 以外のライフタイムを直接持つか
+/// (呼び出し側では宣言されていないので型注釈に書けない: `Cow<'a, str>`)
+fn has_named_lifetime(ty: &Type) -> bool {
+    let is_named = |lifetime: &str| lifetime != "'static" && lifetime != "'_";
+    match ty {
+        Type::BorrowedRef { borrowed_ref } => {
+            borrowed_ref.lifetime.as_deref().is_some_and(is_named)
+        }
+        Type::DynTrait { dyn_trait } => dyn_trait.lifetime.as_deref().is_some_and(is_named),
+        Type::ResolvedPath { resolved_path } => match &resolved_path.args {
+            Some(GenericArgs::AngleBracketed { angle_bracketed }) => angle_bracketed
+                .args
+                .iter()
+                .any(|arg| matches!(arg, GenericArg::Lifetime { lifetime } if is_named(lifetime))),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// 引数名がそのまま変数名として使えるか
+fn is_identifier(name: &str) -> bool {
+    name != "_"
+        && name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// レシーバの変数名: 型名を snake_case にする ("HashMap" -> "hash_map")
+fn variable_name(owner: &str) -> String {
+    let type_name = owner.rsplit("::").next().unwrap_or(owner);
+    let mut name = String::new();
+    for (i, c) in type_name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        } else {
+            name.push(c);
+        }
+    }
+    if name.is_empty() {
+        "value".to_string()
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::{usage_example, variable_name};
+    use crate::rustdoc_json::Function;
+    use crate::signature_builder::{RenderOptions, Type};
+
+    fn func(inputs: Value, output: Value, is_unsafe: bool) -> Function {
+        serde_json::from_value(json!({
+            "sig": { "inputs": inputs, "output": output },
+            "header": { "is_unsafe": is_unsafe, "abi": "Rust" }
+        }))
+        .unwrap()
+    }
+
+    fn result_of(ty: Value) -> Value {
+        json!({ "resolved_path": {
+            "path": "Result",
+            "id": null,
+            "args": { "angle_bracketed": { "args": [{ "type": ty }, { "type": { "resolved_path": { "path": "Error", "id": null, "args": null } } }], "constraints": [] } }
+        } })
+    }
+
+    #[test]
+    fn free_function_unwraps_result_with_question_mark() {
+        let f = func(
+            json!([["input", { "primitive": "str" }]]),
+            result_of(json!({ "primitive": "u32" })),
+            false,
+        );
+        assert_eq!(
+            usage_example("fx::parse", &f, None, RenderOptions::default()),
+            "let out: u32 = fx::parse(input)?;"
+        );
+    }
+
+    #[test]
+    fn method_calls_on_a_receiver_named_after_the_type() {
+        let f = func(
+            json!([["self", { "generic": "Self" }], ["_", { "primitive": "u8" }]]),
+            json!(null),
+            false,
+        );
+        assert_eq!(
+            usage_example("fx::HashMap::clear", &f, None, RenderOptions::default()),
+            "hash_map.clear(todo!());"
+        );
+    }
+
+    #[test]
+    fn self_return_uses_the_impl_type_and_generics_are_inferred() {
+        let self_type: Type = serde_json::from_value(
+            json!({ "resolved_path": { "path": "Foo", "id": null, "args": null } }),
+        )
+        .unwrap();
+        let f = func(json!([]), json!({ "generic": "Self" }), false);
+        assert_eq!(
+            usage_example(
+                "fx::Foo::new",
+                &f,
+                Some(&self_type),
+                RenderOptions::default()
+            ),
+            "let out: Foo = fx::Foo::new();"
+        );
+        let generic = func(json!([]), json!({ "generic": "T" }), true);
+        assert_eq!(
+            usage_example("fx::make", &generic, None, RenderOptions::default()),
+            "unsafe { let out = fx::make(); }"
+        );
+    }
+
+    #[test]
+    fn receiver_names_are_snake_case() {
+        assert_eq!(variable_name("std::collections::HashMap"), "hash_map");
+        assert_eq!(variable_name(""), "value");
+    }
+}